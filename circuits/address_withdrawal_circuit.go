@@ -0,0 +1,89 @@
+package circuits
+
+import (
+	"runtime"
+
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/std/accumulator/merkle"
+	"github.com/consensys/gnark/std/hash/mimc"
+)
+
+var AddressWithdrawalCircuitPackageName string
+
+// init sets Name to the path of this file
+func init() {
+	_, AddressWithdrawalCircuitPackageName, _, _ = runtime.Caller(0) // this file
+}
+
+// AddressWithdrawalCircuit spends a note owned by an Algorand address instead of an
+// eddsa key. The owner is committed to as the point (0, OwnerAddress), which is not
+// on the curve so that no eddsa key can own the same notes. There is no signature
+// in the circuit, the contract checks that the owner authorized the transaction group.
+type AddressWithdrawalCircuit struct {
+	WithdrawalAddress frontend.Variable `gnark:",public"`
+	WithdrawalAmount  frontend.Variable `gnark:",public"`
+	Fee               frontend.Variable `gnark:",public"`
+	AssetId           frontend.Variable `gnark:",public"` // 0 for Algo, for all notes
+	Nullifier         frontend.Variable `gnark:",public"`
+	Root              frontend.Variable `gnark:",public"`
+
+	UnspentCommitment frontend.Variable `gnark:",public"`
+	SpentCommitment   frontend.Variable `gnark:",public"`
+
+	// OwnerAddress is the owner address mod the curve order, it owns the spendable
+	// and unspent notes
+	OwnerAddress frontend.Variable `gnark:",public"`
+
+	// X and Y for output pubkey
+	OutputX frontend.Variable
+	OutputY frontend.Variable
+
+	SpendableK      frontend.Variable
+	SpendableR      frontend.Variable
+	SpendableAmount frontend.Variable
+	SpendableIndex  frontend.Variable
+	SpendablePath   [MerkleTreeLevels + 1]frontend.Variable
+
+	SpentAmount frontend.Variable
+	SpentK      frontend.Variable
+	SpentR      frontend.Variable
+
+	UnspentAmount frontend.Variable
+	UnspentK      frontend.Variable
+	UnspentR      frontend.Variable
+}
+
+func (c *AddressWithdrawalCircuit) Define(api frontend.API) error {
+	mimc, _ := mimc.NewMiMC(api)
+
+	// hash(hash(OwnerAddress, NullifierKeyDomain), SpendableIndex, SpendableK) == Nullifier,
+	// see nullifier.go
+	nk := hashNullifierKey(&mimc, c.OwnerAddress)
+	verifyHashCommitment(api, &mimc, c.Nullifier, 1, nk, c.SpendableIndex, c.SpendableK)
+
+	// hash(hash(UnspentAmount, AssetId, UnspentK, UnspentR, 0, OwnerAddress)) == UnspentCommitment
+	verifyHashCommitment(api, &mimc, c.UnspentCommitment, 2, c.UnspentAmount, c.AssetId, c.UnspentK, c.UnspentR, 0, c.OwnerAddress)
+
+	// hash(hash(SpendAmount, AssetId, SpendK, SpendR, OutputX, OutputY)) == SpendCommitment
+	verifyHashCommitment(api, &mimc, c.SpentCommitment, 2, c.SpentAmount, c.AssetId, c.SpentK, c.SpentR, c.OutputX, c.OutputY)
+
+	// Path[0] == hash(SpendableAmount, AssetId, SpendableK, SpendableR, 0, OwnerAddress)
+	verifyHashCommitment(api, &mimc, c.SpendablePath[0], 1, c.SpendableAmount, c.AssetId, c.SpendableK, c.SpendableR, 0, c.OwnerAddress)
+
+	// SpendableAmount, SpendableK is in the merkle tree at index
+	mp := merkle.MerkleProof{
+		RootHash: c.Root,
+		Path:     c.SpendablePath[:],
+	}
+	mp.VerifyProof(api, &mimc, c.SpendableIndex)
+	// Change == Amount - Withdrawal - Spent - Fee, all non-negative, as in WithdrawalCircuit
+	// W and S are bounded to 64 bits so that their sum cannot wrap around the field
+	api.ToBinary(c.WithdrawalAmount, 64)
+	api.ToBinary(c.SpentAmount, 64)
+	totalSpent := api.Add(c.WithdrawalAmount, c.SpentAmount)
+	api.AssertIsLessOrEqual(totalSpent, c.SpendableAmount)
+	api.AssertIsLessOrEqual(c.Fee, api.Sub(c.SpendableAmount, totalSpent))
+	api.AssertIsEqual(c.UnspentAmount, api.Sub(c.SpendableAmount, totalSpent, c.Fee))
+
+	return nil
+}
@@ -17,6 +17,7 @@ func init() {
 
 type DepositCircuit struct {
 	Amount     frontend.Variable `gnark:",public"`
+	AssetId    frontend.Variable `gnark:",public"` // 0 for Algo
 	Commitment frontend.Variable `gnark:",public"`
 
 	// X and Y for output pubkey
@@ -30,8 +31,13 @@ type DepositCircuit struct {
 func (c *DepositCircuit) Define(api frontend.API) error {
 	mimc, _ := mimc.NewMiMC(api)
 
-	// hash(hash(Amount, K, R, OutputX, OutputY)) == Commitment
-	verifyHashCommitment(api, &mimc, c.Commitment, 2, c.Amount, c.K, c.R, c.OutputX, c.OutputY)
+	// hash(hash(Amount, AssetId, K, R, OutputX, OutputY)) == Commitment
+	verifyHashCommitment(api, &mimc, c.Commitment, 2, c.Amount, c.AssetId, c.K, c.R, c.OutputX, c.OutputY)
+
+	// Amount and AssetId are uint64 on chain, bound them to 64 bits so that a note
+	// cannot be worth more than the deposit paid
+	api.ToBinary(c.Amount, 64)
+	api.ToBinary(c.AssetId, 64)
 
 	return nil
 }
@@ -20,3 +20,13 @@ func verifyHashCommitment(api frontend.API, mimc *mimc.MiMC, commitment frontend
 	api.AssertIsEqual(commitment, h)
 	mimc.Reset()
 }
+
+// spendMessage returns the message signed by the spender of a note, hash(values...) of
+// the public outputs of the spend, so that no prover can change them
+func spendMessage(mimc *mimc.MiMC, values ...frontend.Variable) frontend.Variable {
+	mimc.Reset()
+	mimc.Write(values...)
+	h := mimc.Sum()
+	mimc.Reset()
+	return h
+}
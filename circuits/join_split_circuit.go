@@ -0,0 +1,124 @@
+package circuits
+
+import (
+	"runtime"
+
+	tedwards "github.com/consensys/gnark-crypto/ecc/twistededwards"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/std/accumulator/merkle"
+	"github.com/consensys/gnark/std/algebra/native/twistededwards"
+	"github.com/consensys/gnark/std/hash/mimc"
+	"github.com/consensys/gnark/std/signature/eddsa"
+)
+
+// The number of notes spent and created by a join-split
+const (
+	JoinSplitInputs  = 2
+	JoinSplitOutputs = 2
+)
+
+var JoinSplitCircuitPackageName string
+
+// init sets Name to the path of this file
+func init() {
+	_, JoinSplitCircuitPackageName, _, _ = runtime.Caller(0) // this file
+}
+
+// JoinSplitCircuit spends JoinSplitInputs notes of the spender into JoinSplitOutputs
+// notes of the same asset, each with its own owner, paying Fee. It allows to
+// consolidate notes and to make payments larger than any single note.
+// Each input has its own nullifier and merkle path, all proven against Root.
+type JoinSplitCircuit struct {
+	Fee     frontend.Variable `gnark:",public"`
+	AssetId frontend.Variable `gnark:",public"` // 0 for Algo, for all notes
+	Root    frontend.Variable `gnark:",public"`
+
+	Nullifiers  [JoinSplitInputs]frontend.Variable  `gnark:",public"`
+	Commitments [JoinSplitOutputs]frontend.Variable `gnark:",public"`
+
+	// X and Y for spender pubkey, whose receiving key owns all the inputs
+	SpenderX frontend.Variable
+	SpenderY frontend.Variable
+	// NullifierKey is the nullifier key of the spender, see nullifier.go
+	NullifierKey frontend.Variable
+
+	// Signature is the signature by the input keypair of hash(Fee, AssetId,
+	// Commitments...)
+	Signature eddsa.Signature
+
+	InputAmounts [JoinSplitInputs]frontend.Variable
+	InputKs      [JoinSplitInputs]frontend.Variable
+	InputRs      [JoinSplitInputs]frontend.Variable
+	InputIndexes [JoinSplitInputs]frontend.Variable
+	InputPaths   [JoinSplitInputs][MerkleTreeLevels + 1]frontend.Variable
+
+	OutputAmounts [JoinSplitOutputs]frontend.Variable
+	OutputKs      [JoinSplitOutputs]frontend.Variable
+	OutputRs      [JoinSplitOutputs]frontend.Variable
+	// X and Y for the output pubkeys
+	OutputXs [JoinSplitOutputs]frontend.Variable
+	OutputYs [JoinSplitOutputs]frontend.Variable
+}
+
+func (c *JoinSplitCircuit) Define(api frontend.API) error {
+	mimc, _ := mimc.NewMiMC(api)
+
+	// Verify the the Input pubkey signed the public outputs of the join-split
+	curve, err := twistededwards.NewEdCurve(api, tedwards.BLS12_381)
+	if err != nil {
+		return err
+	}
+
+	pubkey := eddsa.PublicKey{}
+	pubkey.A.X = c.SpenderX
+	pubkey.A.Y = c.SpenderY
+
+	msg := spendMessage(&mimc, append([]frontend.Variable{c.Fee, c.AssetId}, c.Commitments[:]...)...)
+	err = eddsa.Verify(curve, c.Signature, msg, pubkey, &mimc)
+
+	if err != nil {
+		return err
+	}
+
+	mimc.Reset()
+
+	// The inputs are owned by the receiving key of the spender, see nullifier.go
+	owner := receivingKey(api, curve, &mimc, c.SpenderX, c.SpenderY, c.NullifierKey)
+
+	inputsTotal := frontend.Variable(0)
+	for i := range JoinSplitInputs {
+		// hash(NullifierKey, InputIndex, InputK) == Nullifier, see nullifier.go
+		verifyHashCommitment(api, &mimc, c.Nullifiers[i], 1, c.NullifierKey, c.InputIndexes[i], c.InputKs[i])
+
+		// Path[0] == hash(InputAmount, AssetId, InputK, InputR, owner.X, owner.Y)
+		verifyHashCommitment(api, &mimc, c.InputPaths[i][0], 1, c.InputAmounts[i], c.AssetId, c.InputKs[i], c.InputRs[i], owner.X, owner.Y)
+
+		// InputAmount, InputK is in the merkle tree at index
+		mp := merkle.MerkleProof{
+			RootHash: c.Root,
+			Path:     c.InputPaths[i][:],
+		}
+		mp.VerifyProof(api, &mimc, c.InputIndexes[i])
+		mimc.Reset()
+
+		api.ToBinary(c.InputAmounts[i], 64)
+		inputsTotal = api.Add(inputsTotal, c.InputAmounts[i])
+	}
+
+	// The inputs, the outputs and the fee are bounded to 64 bits so that their sums
+	// cannot wrap around the field
+	outputsTotal := c.Fee
+	api.ToBinary(c.Fee, 64)
+	for i := range JoinSplitOutputs {
+		// hash(hash(OutputAmount, AssetId, OutputK, OutputR, OutputX, OutputY)) == Commitment
+		verifyHashCommitment(api, &mimc, c.Commitments[i], 2, c.OutputAmounts[i], c.AssetId, c.OutputKs[i], c.OutputRs[i], c.OutputXs[i], c.OutputYs[i])
+
+		api.ToBinary(c.OutputAmounts[i], 64)
+		outputsTotal = api.Add(outputsTotal, c.OutputAmounts[i])
+	}
+
+	// Sum(InputAmounts) == Sum(OutputAmounts) + Fee
+	api.AssertIsEqual(inputsTotal, outputsTotal)
+
+	return nil
+}
@@ -0,0 +1,46 @@
+package circuits
+
+import (
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/std/algebra/native/twistededwards"
+	"github.com/consensys/gnark/std/hash/mimc"
+)
+
+// NullifierKeyDomain is hashed with the owner secret to get its nullifier key
+const NullifierKeyDomain = 'n'
+
+// The nullifier of a note is hash(NullifierKey, LeafIndex, K), where the nullifier key
+// is derived from a secret of the note owner: nk = hash(s, NullifierKeyDomain) for
+// notes owned by the eddsa key s*Base, and hash(OwnerAddress, NullifierKeyDomain) for
+// notes owned by an Algorand address. Binding the leaf index makes the nullifiers of
+// two notes different even if they share K, and binding the key keeps an observer who
+// learns K, such as the note creator, from computing the nullifier of the note.
+//
+// Notes of an eddsa key are not owned by its public key A = s*Base but by its
+// receiving key hash(A, nk)*Base, which commits to nk. A spend proves the receiving
+// key from A and nk, and a signature by A: the proof never takes s, so the holder of
+// nk, such as an auditor, cannot spend, and no other nk gives the same receiving key.
+
+// receivingKey returns the receiving key of the spender pubkey (x, y) with nullifier
+// key nk, hash(x, y, nk)*Base
+func receivingKey(api frontend.API, curve twistededwards.Curve, mimc *mimc.MiMC,
+	x, y, nk frontend.Variable) twistededwards.Point {
+
+	mimc.Reset()
+	mimc.Write(x, y, nk)
+	ivk := mimc.Sum()
+	mimc.Reset()
+
+	params := curve.Params()
+	base := twistededwards.Point{X: params.Base[0], Y: params.Base[1]}
+	return curve.ScalarMul(base, ivk)
+}
+
+// hashNullifierKey returns hash(secret, NullifierKeyDomain)
+func hashNullifierKey(mimc *mimc.MiMC, secret frontend.Variable) frontend.Variable {
+	mimc.Reset()
+	mimc.Write(secret, NullifierKeyDomain)
+	nk := mimc.Sum()
+	mimc.Reset()
+	return nk
+}
@@ -0,0 +1,115 @@
+package circuits
+
+import (
+	"runtime"
+
+	tedwards "github.com/consensys/gnark-crypto/ecc/twistededwards"
+	"github.com/consensys/gnark/frontend"
+	"github.com/consensys/gnark/std/accumulator/merkle"
+	"github.com/consensys/gnark/std/algebra/native/twistededwards"
+	"github.com/consensys/gnark/std/hash/mimc"
+	"github.com/consensys/gnark/std/signature/eddsa"
+)
+
+var TransferCircuitPackageName string
+
+// init sets Name to the path of this file
+func init() {
+	_, TransferCircuitPackageName, _, _ = runtime.Caller(0) // this file
+}
+
+// TransferCircuit spends a note into a note for the output pubkey and a change note,
+// as WithdrawalCircuit but without withdrawal address and amount: no value leaves
+// the app except the fee
+type TransferCircuit struct {
+	Fee       frontend.Variable `gnark:",public"`
+	AssetId   frontend.Variable `gnark:",public"` // 0 for Algo, for all notes
+	Nullifier frontend.Variable `gnark:",public"`
+	Root      frontend.Variable `gnark:",public"`
+
+	UnspentCommitment frontend.Variable `gnark:",public"`
+	SpentCommitment   frontend.Variable `gnark:",public"`
+
+	// X and Y for spender pubkey, whose receiving key owns the spendable and unspent notes
+	SpenderX frontend.Variable
+	SpenderY frontend.Variable
+	// NullifierKey is the nullifier key of the spender, see nullifier.go
+	NullifierKey frontend.Variable
+
+	// Signature is the signature by the input keypair of hash(Fee, AssetId,
+	// UnspentCommitment, SpentCommitment)
+	Signature eddsa.Signature
+
+	// X and Y for output pubkey
+	OutputX frontend.Variable
+	OutputY frontend.Variable
+
+	SpendableK      frontend.Variable
+	SpendableR      frontend.Variable
+	SpendableAmount frontend.Variable
+	SpendableIndex  frontend.Variable
+	SpendablePath   [MerkleTreeLevels + 1]frontend.Variable
+
+	SpentAmount frontend.Variable
+	SpentK      frontend.Variable
+	SpentR      frontend.Variable
+
+	UnspentAmount frontend.Variable
+	UnspentK      frontend.Variable
+	UnspentR      frontend.Variable
+}
+
+func (c *TransferCircuit) Define(api frontend.API) error {
+	mimc, _ := mimc.NewMiMC(api)
+
+	curve, err := twistededwards.NewEdCurve(api, tedwards.BLS12_381)
+	if err != nil {
+		return err
+	}
+
+	// The notes of the spender are owned by its receiving key, see nullifier.go
+	owner := receivingKey(api, curve, &mimc, c.SpenderX, c.SpenderY, c.NullifierKey)
+
+	// hash(hash(UnspentAmount, AssetId, UnspentK, UnspentR, owner.X, owner.Y)) == UnspentCommitment
+	verifyHashCommitment(api, &mimc, c.UnspentCommitment, 2, c.UnspentAmount, c.AssetId, c.UnspentK, c.UnspentR, owner.X, owner.Y)
+
+	// hash(hash(SpendAmount, AssetId, SpendK, SpendR, OutputX, OutputY)) == SpendCommitment
+	verifyHashCommitment(api, &mimc, c.SpentCommitment, 2, c.SpentAmount, c.AssetId, c.SpentK, c.SpentR, c.OutputX, c.OutputY)
+
+	// Verify the the Input pubkey signed the public outputs of the transfer
+	pubkey := eddsa.PublicKey{}
+	pubkey.A.X = c.SpenderX
+	pubkey.A.Y = c.SpenderY
+
+	msg := spendMessage(&mimc, c.Fee, c.AssetId, c.UnspentCommitment, c.SpentCommitment)
+	err = eddsa.Verify(curve, c.Signature, msg, pubkey, &mimc)
+
+	if err != nil {
+		return err
+	}
+
+	mimc.Reset()
+
+	// hash(NullifierKey, SpendableIndex, SpendableK) == Nullifier, see nullifier.go
+	verifyHashCommitment(api, &mimc, c.Nullifier, 1, c.NullifierKey, c.SpendableIndex, c.SpendableK)
+
+	// Path[0] == hash(SpendableAmount, AssetId, SpendableK, SpendableR, owner.X, owner.Y)
+	verifyHashCommitment(api, &mimc, c.SpendablePath[0], 1, c.SpendableAmount, c.AssetId, c.SpendableK, c.SpendableR, owner.X, owner.Y)
+
+	// SpendableAmount, SpendableK is in the merkle tree at index
+	mp := merkle.MerkleProof{
+		RootHash: c.Root,
+		Path:     c.SpendablePath[:],
+	}
+	mp.VerifyProof(api, &mimc, c.SpendableIndex)
+	// Change == Amount - Spent - Fee, and C, A, S, F are all non-negative
+	// We express it by:
+	// 		S <= A
+	//		F <= A - S
+	//		C = A - S - Fee
+	api.AssertIsLessOrEqual(c.SpentAmount, c.SpendableAmount)
+	api.AssertIsLessOrEqual(c.Fee, api.Sub(c.SpendableAmount, c.SpentAmount))
+	api.AssertIsEqual(c.UnspentAmount, api.Sub(c.SpendableAmount, c.SpentAmount, c.Fee))
+
+	return nil
+}
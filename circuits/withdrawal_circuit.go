@@ -26,17 +26,21 @@ type WithdrawalCircuit struct {
 	WithdrawalAddress frontend.Variable `gnark:",public"`
 	WithdrawalAmount  frontend.Variable `gnark:",public"`
 	Fee               frontend.Variable `gnark:",public"`
+	AssetId           frontend.Variable `gnark:",public"` // 0 for Algo, for all notes
 	Nullifier         frontend.Variable `gnark:",public"`
 	Root              frontend.Variable `gnark:",public"`
 
 	UnspentCommitment frontend.Variable `gnark:",public"`
 	SpentCommitment   frontend.Variable `gnark:",public"`
 
-	// X and Y for spender pubkey
+	// X and Y for spender pubkey, whose receiving key owns the spendable and unspent notes
 	SpenderX frontend.Variable
 	SpenderY frontend.Variable
+	// NullifierKey is the nullifier key of the spender, see nullifier.go
+	NullifierKey frontend.Variable
 
-	// Signature is the signature of the  signed by the input keypair
+	// Signature is the signature by the input keypair of hash(WithdrawalAddress,
+	// WithdrawalAmount, Fee, AssetId, UnspentCommitment, SpentCommitment)
 	Signature eddsa.Signature
 
 	// X and Y for output pubkey
@@ -61,26 +65,28 @@ type WithdrawalCircuit struct {
 func (c *WithdrawalCircuit) Define(api frontend.API) error {
 	mimc, _ := mimc.NewMiMC(api)
 
-	// hash(Amount,K) == Nullifier
-	verifyHashCommitment(api, &mimc, c.Nullifier, 1, c.SpendableAmount, c.SpendableK)
-
-	// hash(hash(UnspentAmount, UnspentK, UnspentR, SpenderX, SpenderY)) == UnspentCommitment
-	verifyHashCommitment(api, &mimc, c.UnspentCommitment, 2, c.UnspentAmount, c.UnspentK, c.UnspentR, c.SpenderX, c.SpenderY)
-
-	// hash(hash(SpendAmount, SpendK, SpendR, OutputX, OutputY)) == SpendCommitment
-	verifyHashCommitment(api, &mimc, c.SpentCommitment, 2, c.SpentAmount, c.SpentK, c.SpentR, c.OutputX, c.OutputY)
-
-	// Verify the the Input pubkey signed the withdrawal commitment
 	curve, err := twistededwards.NewEdCurve(api, tedwards.BLS12_381)
 	if err != nil {
 		return err
 	}
 
+	// The notes of the spender are owned by its receiving key, see nullifier.go
+	owner := receivingKey(api, curve, &mimc, c.SpenderX, c.SpenderY, c.NullifierKey)
+
+	// hash(hash(UnspentAmount, AssetId, UnspentK, UnspentR, owner.X, owner.Y)) == UnspentCommitment
+	verifyHashCommitment(api, &mimc, c.UnspentCommitment, 2, c.UnspentAmount, c.AssetId, c.UnspentK, c.UnspentR, owner.X, owner.Y)
+
+	// hash(hash(SpendAmount, AssetId, SpendK, SpendR, OutputX, OutputY)) == SpendCommitment
+	verifyHashCommitment(api, &mimc, c.SpentCommitment, 2, c.SpentAmount, c.AssetId, c.SpentK, c.SpentR, c.OutputX, c.OutputY)
+
+	// Verify the the Input pubkey signed the public outputs of the withdrawal
 	pubkey := eddsa.PublicKey{}
 	pubkey.A.X = c.SpenderX
 	pubkey.A.Y = c.SpenderY
 
-	err = eddsa.Verify(curve, c.Signature, c.UnspentCommitment, pubkey, &mimc)
+	msg := spendMessage(&mimc, c.WithdrawalAddress, c.WithdrawalAmount, c.Fee, c.AssetId,
+		c.UnspentCommitment, c.SpentCommitment)
+	err = eddsa.Verify(curve, c.Signature, msg, pubkey, &mimc)
 
 	if err != nil {
 		return err
@@ -88,8 +94,11 @@ func (c *WithdrawalCircuit) Define(api frontend.API) error {
 
 	mimc.Reset()
 
-	// Path[0] == hash(SpendableAmount, SpendableK, SpendableR, SpenderX, SpenderY)
-	verifyHashCommitment(api, &mimc, c.SpendablePath[0], 1, c.SpendableAmount, c.SpendableK, c.SpendableR, c.SpenderX, c.SpenderY)
+	// hash(NullifierKey, SpendableIndex, SpendableK) == Nullifier, see nullifier.go
+	verifyHashCommitment(api, &mimc, c.Nullifier, 1, c.NullifierKey, c.SpendableIndex, c.SpendableK)
+
+	// Path[0] == hash(SpendableAmount, AssetId, SpendableK, SpendableR, owner.X, owner.Y)
+	verifyHashCommitment(api, &mimc, c.SpendablePath[0], 1, c.SpendableAmount, c.AssetId, c.SpendableK, c.SpendableR, owner.X, owner.Y)
 
 	// SpendableAmount, SpendableK is in the merkle tree at index
 	mp := merkle.MerkleProof{
@@ -102,6 +111,9 @@ func (c *WithdrawalCircuit) Define(api frontend.API) error {
 	// 		W <= A
 	//		F <= A - W
 	//		C = A - W - Fee
+	// W and S are bounded to 64 bits so that their sum cannot wrap around the field
+	api.ToBinary(c.WithdrawalAmount, 64)
+	api.ToBinary(c.SpentAmount, 64)
 	totalSpent := api.Add(c.WithdrawalAmount, c.SpentAmount)
 	api.AssertIsLessOrEqual(totalSpent, c.SpendableAmount)
 	api.AssertIsLessOrEqual(c.Fee, api.Sub(c.SpendableAmount, totalSpent))
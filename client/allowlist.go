@@ -0,0 +1,113 @@
+package client
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// depositorBoxPrefix prefixes the address of the allowlist boxes of the depositors
+const depositorBoxPrefix = "d"
+
+// DepositorBoxName returns the name of the allowlist box of depositor
+func DepositorBoxName(depositor types.Address) []byte {
+	return append([]byte(depositorBoxPrefix), depositor[:]...)
+}
+
+// AddDepositor adds depositor to the deposit allowlist of the app, which only
+// deployments with config.DepositAllowlist have. Admin is the app admin and pays the
+// depositor MBR and the transaction fees
+func (f *Frontend) AddDepositor(admin *crypto.Account, depositor types.Address) error {
+	return f.sendAdminCall(admin, AddDepositorMethod, []any{depositor},
+		DepositorBoxName(depositor), config.DepositorMbr)
+}
+
+// RemoveDepositor removes depositor from the deposit allowlist of the app, the app
+// refunds the depositor MBR to admin, the app admin
+func (f *Frontend) RemoveDepositor(admin *crypto.Account, depositor types.Address) error {
+	return f.sendAdminCall(admin, RemoveDepositorMethod, []any{depositor},
+		DepositorBoxName(depositor), 0)
+}
+
+// SetAdmin hands the admin role of the app over from admin to newAdmin
+func (f *Frontend) SetAdmin(admin *crypto.Account, newAdmin types.Address) error {
+	return f.sendAdminCall(admin, SetAdminMethod, []any{newAdmin}, nil, 0)
+}
+
+// DepositorAllowed returns true if depositor is in the deposit allowlist of the app,
+// always false for deployments without config.DepositAllowlist, where anyone can
+// deposit
+func (f *Frontend) DepositorAllowed(ctx context.Context, depositor types.Address) (
+	bool, error) {
+
+	exists, err := f.boxExists(ctx, DepositorBoxName(depositor))
+	if err != nil {
+		return false, fmt.Errorf("failed to read depositor box: %v", err)
+	}
+	return exists, nil
+}
+
+// sendAdminCall sends a call of method with args by admin, referencing the box
+// named box if not nil. If mbr is not zero the call is preceded by a payment of mbr
+// from admin to the app, otherwise the call covers the fee of an inner transaction.
+func (f *Frontend) sendAdminCall(admin *crypto.Account, method string, args []any,
+	box []byte, mbr uint64) error {
+
+	if !config.DepositAllowlist {
+		return fmt.Errorf("%s: the app has no deposit allowlist", method)
+	}
+	sp, err := f.algod.SuggestedParams().Do(context.Background())
+	if err != nil {
+		return fmt.Errorf("failed to get suggested params: %v", err)
+	}
+	sp.FlatFee = true
+
+	var atc = transaction.AtomicTransactionComposer{}
+	signer := transaction.BasicAccountTransactionSigner{Account: *admin}
+
+	sp.Fee = 2 * transaction.MinTxnFee
+	if mbr > 0 {
+		txn, err := transaction.MakePaymentTxn(admin.Address.String(),
+			crypto.GetApplicationAddress(f.App.Id).String(), mbr, nil,
+			types.ZeroAddress.String(), sp,
+		)
+		if err != nil {
+			return fmt.Errorf("failed to make payment txn: %v", err)
+		}
+		err = atc.AddTransaction(transaction.TransactionWithSigner{Txn: txn, Signer: signer})
+		if err != nil {
+			return fmt.Errorf("failed to add payment txn: %v", err)
+		}
+		sp.Fee = 0
+	}
+
+	m, err := f.App.Schema.Contract.GetMethodByName(method)
+	if err != nil {
+		return fmt.Errorf("failed to get method %s: %v", method, err)
+	}
+	txnParams := transaction.AddMethodCallParams{
+		AppID:           f.App.Id,
+		Sender:          admin.Address,
+		SuggestedParams: sp,
+		OnComplete:      types.NoOpOC,
+		Signer:          signer,
+		Method:          m,
+		MethodArgs:      args,
+	}
+	if box != nil {
+		txnParams.BoxReferences = []types.AppBoxReference{{AppID: f.App.Id, Name: box}}
+	}
+	if err := atc.AddMethodCall(txnParams); err != nil {
+		return fmt.Errorf("failed to add %s method call: %v", method, err)
+	}
+
+	if _, err := atc.Execute(f.algod, context.Background(), 4); err != nil {
+		return fmt.Errorf("failed to execute transaction: %v", err)
+	}
+	return nil
+}
@@ -0,0 +1,241 @@
+package client
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	ap "github.com/giuliop/algoplonk"
+	"github.com/giuliop/algoplonk/utils"
+)
+
+// The artefact file names exported by setup in deployed/<network>.
+// They must match the names used in the setup package.
+const (
+	appFilename                        = "App.json"
+	appArc32FileName                   = "APP.arc32.json"
+	tssBytecodeFileName                = "TSS.tok"
+	depositVerifierBytecodeFileName    = "DepositVerifier.tok"
+	withdrawalVerifierBytecodeFileName = "WithdrawalVerifier.tok"
+	treeConfigFileName                 = "TreeConfig.json"
+	compiledDepositCircuitFileName     = "CompiledDepositCircuit.bin"
+	compiledWithdrawalCircuitFileName  = "CompiledWithdrawalCircuit.bin"
+
+	addressWithdrawalVerifierBytecodeFileName = "AddressWithdrawalVerifier.tok"
+	compiledAddressWithdrawalCircuitFileName  = "CompiledAddressWithdrawalCircuit.bin"
+	transferVerifierBytecodeFileName          = "TransferVerifier.tok"
+	compiledTransferCircuitFileName           = "CompiledTransferCircuit.bin"
+	joinSplitVerifierBytecodeFileName         = "JoinSplitVerifier.tok"
+	compiledJoinSplitCircuitFileName          = "CompiledJoinSplitCircuit.bin"
+)
+
+// App holds everything a client needs to interact with a deployed APP
+type App struct {
+	Id                 uint64
+	CreationBlock      uint64
+	Schema             *avm.Arc32Schema
+	TSS                *Lsig
+	DepositCc          *ap.CompiledCircuit
+	WithdrawalCc       *ap.CompiledCircuit
+	DepositVerifier    *Lsig
+	WithdrawalVerifier *Lsig
+	TreeConfig         TreeConfig
+
+	// AddressWithdrawalCc and AddressWithdrawalVerifier spend notes owned by
+	// Algorand addresses
+	AddressWithdrawalCc       *ap.CompiledCircuit
+	AddressWithdrawalVerifier *Lsig
+
+	// TransferCc and TransferVerifier spend notes into other notes without withdrawal
+	TransferCc       *ap.CompiledCircuit
+	TransferVerifier *Lsig
+
+	// JoinSplitCc and JoinSplitVerifier spend several notes into several notes
+	JoinSplitCc       *ap.CompiledCircuit
+	JoinSplitVerifier *Lsig
+}
+
+// Lsig is a logicsig account with its address
+type Lsig struct {
+	Account crypto.LogicSigAccount
+	Address types.Address
+}
+
+type appJson struct {
+	Id            uint64 `json:"id"`
+	CreationBlock uint64 `json:"creationBlock"`
+}
+
+// ErrUnsupportedMethod is returned by the methods whose verifier or compiled circuit
+// is missing from the artefacts of the app, such as the spends added after the
+// first deployments, or the proofs of a client without compiled circuits
+var ErrUnsupportedMethod = errors.New("method not supported by the app artefacts")
+
+// Supports returns ErrUnsupportedMethod if the app has no verifier or no compiled
+// circuit for the app method
+func (a *App) Supports(method string) error {
+	var verifier *Lsig
+	switch method {
+	case DepositMethod:
+		verifier = a.DepositVerifier
+	case WithDrawalMethod:
+		verifier = a.WithdrawalVerifier
+	case AddressWithdrawalMethod:
+		verifier = a.AddressWithdrawalVerifier
+	case TransferMethod:
+		verifier = a.TransferVerifier
+	case JoinSplitMethod:
+		verifier = a.JoinSplitVerifier
+	default:
+		return fmt.Errorf("%w: %s", ErrUnsupportedMethod, method)
+	}
+	if verifier == nil {
+		return fmt.Errorf("%w: no %s verifier", ErrUnsupportedMethod, method)
+	}
+	if _, err := a.CompiledCircuit(method); err != nil {
+		return err
+	}
+	return nil
+}
+
+// ReadApp reads the artefacts exported by setup from dir and returns an App. The
+// deposit and withdrawal verifiers are required, the other verifiers and the
+// compiled circuits are optional (see Supports).
+func ReadApp(dir string) (*App, error) {
+	app := App{}
+	appJson := appJson{}
+
+	if err := decodeJSONFile(filepath.Join(dir, appFilename), &appJson); err != nil {
+		return nil, err
+	}
+	if err := decodeJSONFile(filepath.Join(dir, appArc32FileName), &app.Schema); err != nil {
+		return nil, err
+	}
+	app.Id = appJson.Id
+	app.CreationBlock = appJson.CreationBlock
+
+	var err error
+	if app.TSS, err = readLogicSigFromFile(filepath.Join(dir, tssBytecodeFileName)); err != nil {
+		return nil, err
+	}
+	app.DepositVerifier, err = readLogicSigFromFile(
+		filepath.Join(dir, depositVerifierBytecodeFileName))
+	if err != nil {
+		return nil, err
+	}
+	app.WithdrawalVerifier, err = readLogicSigFromFile(
+		filepath.Join(dir, withdrawalVerifierBytecodeFileName))
+	if err != nil {
+		return nil, err
+	}
+	app.TreeConfig, err = readTreeConfiguration(filepath.Join(dir, treeConfigFileName))
+	if err != nil {
+		return nil, err
+	}
+
+	// the verifiers and compiled circuits added after the first deployments are
+	// optional, the methods needing them fail with ErrUnsupportedMethod
+	optionalVerifiers := []struct {
+		lsig     **Lsig
+		filename string
+	}{
+		{&app.AddressWithdrawalVerifier, addressWithdrawalVerifierBytecodeFileName},
+		{&app.TransferVerifier, transferVerifierBytecodeFileName},
+		{&app.JoinSplitVerifier, joinSplitVerifierBytecodeFileName},
+	}
+	for _, v := range optionalVerifiers {
+		path := filepath.Join(dir, v.filename)
+		if !fileExists(path) {
+			continue
+		}
+		if *v.lsig, err = readLogicSigFromFile(path); err != nil {
+			return nil, err
+		}
+	}
+	compiledCircuits := []struct {
+		cc       **ap.CompiledCircuit
+		filename string
+		name     string
+	}{
+		{&app.DepositCc, compiledDepositCircuitFileName, "deposit"},
+		{&app.WithdrawalCc, compiledWithdrawalCircuitFileName, "withdrawal"},
+		{&app.AddressWithdrawalCc, compiledAddressWithdrawalCircuitFileName,
+			"address withdrawal"},
+		{&app.TransferCc, compiledTransferCircuitFileName, "transfer"},
+		{&app.JoinSplitCc, compiledJoinSplitCircuitFileName, "join-split"},
+	}
+	for _, c := range compiledCircuits {
+		path := filepath.Join(dir, c.filename)
+		if !fileExists(path) {
+			continue
+		}
+		if *c.cc, err = utils.DeserializeCompiledCircuit(path); err != nil {
+			return nil, fmt.Errorf("error deserializing compiled %s circuit: %v", c.name, err)
+		}
+	}
+
+	return &app, nil
+}
+
+// fileExists returns true if there is a file at path
+func fileExists(path string) bool {
+	_, err := os.Stat(path)
+	return err == nil
+}
+
+// readLogicSigFromFile reads the compiled logicsig file and returns an Lsig
+func readLogicSigFromFile(compiledFile string) (*Lsig, error) {
+	bytecode, err := os.ReadFile(compiledFile)
+	if err != nil {
+		return nil, fmt.Errorf("error reading file %s: %v", compiledFile, err)
+	}
+	return NewLsig(bytecode)
+}
+
+// NewLsig takes teal bytecode and returns a Lsig
+func NewLsig(bytecode []byte) (*Lsig, error) {
+	lsigAccount, err := crypto.MakeLogicSigAccountEscrowChecked(bytecode, nil)
+	if err != nil {
+		return nil, fmt.Errorf("error creating logic sig account: %v", err)
+	}
+	address, err := lsigAccount.Address()
+	if err != nil {
+		return nil, fmt.Errorf("error getting lsig address: %v", err)
+	}
+	return &Lsig{
+		Account: lsigAccount,
+		Address: address,
+	}, nil
+}
+
+// readTreeConfiguration reads the tree configuration from the given file
+func readTreeConfiguration(treeConfigPath string) (TreeConfig, error) {
+	treeConfig := TreeConfig{}
+	if err := decodeJSONFile(treeConfigPath, &treeConfig); err != nil {
+		return treeConfig, err
+	}
+	treeConfig.HashFunc = config.Hash
+	return treeConfig, nil
+}
+
+// decodeJSONFile decodes the JSON filepath into the given interface
+func decodeJSONFile(filepath string, v interface{}) error {
+	file, err := os.Open(filepath)
+	if err != nil {
+		return fmt.Errorf("error opening file %s: %v", filepath, err)
+	}
+	defer file.Close()
+
+	decoder := json.NewDecoder(file)
+	if err := decoder.Decode(v); err != nil {
+		return fmt.Errorf("error decoding file %s: %v", filepath, err)
+	}
+	return nil
+}
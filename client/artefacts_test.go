@@ -0,0 +1,49 @@
+package client
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/deployed"
+)
+
+func TestReadAppWithoutOptionalArtefacts(t *testing.T) {
+	// the mainnet artefacts predate the address withdrawal, transfer and join-split
+	// verifiers; the compiled circuits are left out too
+	dir := t.TempDir()
+	for _, name := range []string{appFilename, appArc32FileName, tssBytecodeFileName,
+		depositVerifierBytecodeFileName, withdrawalVerifierBytecodeFileName,
+		treeConfigFileName} {
+
+		data, err := os.ReadFile(filepath.Join(deployed.MainNetDirPath, name))
+		if err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(filepath.Join(dir, name), data, 0o600); err != nil {
+			t.Fatal(err)
+		}
+	}
+	app, err := ReadApp(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if app.DepositVerifier == nil || app.WithdrawalVerifier == nil {
+		t.Fatal("required verifiers not read")
+	}
+	for _, method := range []string{DepositMethod, WithDrawalMethod,
+		AddressWithdrawalMethod, TransferMethod, JoinSplitMethod} {
+
+		if err := app.Supports(method); !errors.Is(err, ErrUnsupportedMethod) {
+			t.Fatalf("expected %s to be unsupported, got %v", method, err)
+		}
+	}
+
+	if err := os.Remove(filepath.Join(dir, withdrawalVerifierBytecodeFileName)); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := ReadApp(dir); err == nil {
+		t.Fatal("expected the withdrawal verifier to be required")
+	}
+}
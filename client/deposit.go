@@ -0,0 +1,243 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// SendDeposit creates an Algo deposit transaction and sends it to the network
+func (f *Frontend) SendDeposit(from *crypto.Account, amount uint64, outputPubkey eddsa.PublicKey, inputPrivkey eddsa.PrivateKey) (
+	*Deposit, error) {
+	return f.SendAssetDeposit(from, amount, 0, outputPubkey, inputPrivkey)
+}
+
+// SendAssetDeposit creates a deposit transaction of amount of assetId (0 for Algo)
+// and sends it to the network. The app must be opted in the asset (see OptInAsset)
+func (f *Frontend) SendAssetDeposit(from *crypto.Account, amount, assetId uint64,
+	outputPubkey eddsa.PublicKey, inputPrivkey eddsa.PrivateKey) (*Deposit, error) {
+
+	note, encryptedNote, err := f.NewAssetNote(amount, assetId, inputPrivkey, outputPubkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create note: %v", err)
+	}
+	return f.sendDeposit(from, note, encryptedNote)
+}
+
+// SendAddressDeposit creates a deposit transaction of amount of assetId (0 for Algo)
+// into a note owned by the Algorand address owner and sends it to the network.
+// The note is encrypted to notePubkey, the ReceivingKey of the key used by owner to
+// discover its notes
+func (f *Frontend) SendAddressDeposit(from *crypto.Account, amount, assetId uint64,
+	owner types.Address, notePubkey eddsa.PublicKey) (*Deposit, error) {
+
+	note, encryptedNote, err := f.NewAddressNote(amount, assetId, from.Address, owner,
+		notePubkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create note: %v", err)
+	}
+	return f.sendDeposit(from, note, encryptedNote)
+}
+
+// sendDeposit deposits note from the from account
+func (f *Frontend) sendDeposit(from *crypto.Account, note *Note, encryptedNote *EncryptedNote) (
+	*Deposit, error) {
+
+	amount, assetId := note.Amount, note.AssetId
+	assignment := &circuits.DepositCircuit{
+		Amount:     amount,
+		AssetId:    assetId,
+		Commitment: note.Commitment,
+		K:          note.K,
+		R:          note.R,
+		OutputX:    note.OutputX,
+		OutputY:    note.OutputY,
+	}
+	args, err := f.prove(DepositMethod, assignment)
+	if err != nil {
+		return nil, err
+	}
+	args = append(args, from.Address)
+
+	var atc = transaction.AtomicTransactionComposer{}
+
+	sp, err := f.algod.SuggestedParams().Do(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to get suggested params: %v", err)
+	}
+	sp.Fee = 0
+	sp.FlatFee = true
+
+	depositMethod, err := f.App.Schema.Contract.GetMethodByName(DepositMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", DepositMethod, err)
+	}
+
+	txnParams := transaction.AddMethodCallParams{
+		AppID:           f.App.Id,
+		Sender:          f.App.DepositVerifier.Address,
+		SuggestedParams: sp,
+		OnComplete:      types.NoOpOC,
+		Signer: transaction.LogicSigAccountTransactionSigner{
+			LogicSigAccount: f.App.DepositVerifier.Account},
+		Method:     depositMethod,
+		MethodArgs: args,
+		BoxReferences: []types.AppBoxReference{
+			{AppID: f.App.Id, Name: []byte("subtree")},
+			{AppID: f.App.Id, Name: []byte("subtree")},
+			{AppID: f.App.Id, Name: []byte("roots")},
+		},
+		Note: encryptedNote.Bytes(),
+	}
+	if config.DepositAllowlist {
+		txnParams.BoxReferences = append(txnParams.BoxReferences,
+			types.AppBoxReference{AppID: f.App.Id, Name: DepositorBoxName(from.Address)})
+	}
+	if err := atc.AddMethodCall(txnParams); err != nil {
+		return nil, fmt.Errorf("failed to add %s method call: %v", DepositMethod, err)
+	}
+
+	// now let's add the payment (or asset transfer) transaction
+	signer := transaction.BasicAccountTransactionSigner{Account: *from}
+	appAddress := crypto.GetApplicationAddress(f.App.Id).String()
+	var txn types.Transaction
+	if assetId == 0 {
+		txn, err = transaction.MakePaymentTxn(from.Address.String(), appAddress, amount,
+			nil, types.ZeroAddress.String(), sp)
+	} else {
+		txn, err = transaction.MakeAssetTransferTxn(from.Address.String(), appAddress,
+			amount, nil, sp, types.ZeroAddress.String(), assetId)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to make payment txn: %v", err)
+	}
+	txn.Fee = transaction.MinTxnFee * config.DepositMinFeeMultiplier
+	err = atc.AddTransaction(transaction.TransactionWithSigner{Txn: txn, Signer: signer})
+	if err != nil {
+		return nil, fmt.Errorf("failed to add payment txn: %v", err)
+	}
+
+	// let's make the required dummy transactions to meet the verifier opcode budget.
+	// these need to be top level transactions to count for lsig opcode pooling.
+	// we make them app calls to count also for smart contract opcode pooling.
+	txnNeeded := config.VerifierTopLevelTxnNeeded - 2 // 2 transactions already added
+	noopMethod, err := f.App.Schema.Contract.GetMethodByName(NoOpMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", NoOpMethod, err)
+	}
+	signerTSS := transaction.LogicSigAccountTransactionSigner{
+		LogicSigAccount: f.App.TSS.Account}
+	senderTSS := f.App.TSS.Address
+
+	for i := 0; i < txnNeeded; i++ {
+		txnParams = transaction.AddMethodCallParams{
+			AppID:           f.App.Id,
+			Sender:          senderTSS,
+			SuggestedParams: sp,
+			OnComplete:      types.NoOpOC,
+			Signer:          signerTSS,
+			Method:          noopMethod,
+			MethodArgs:      []interface{}{i},
+		}
+		if err := atc.AddMethodCall(txnParams); err != nil {
+			return nil, fmt.Errorf("failed to add %s method call: %v", NoOpMethod,
+				err)
+		}
+	}
+
+	if err := f.simulate(&atc, DepositMethod, txnNeeded); err != nil {
+		return nil, err
+	}
+
+	res, err := atc.Execute(f.algod, context.Background(), 4)
+	if err != nil {
+		return nil, fmt.Errorf("failed to execute transaction: %v", err)
+	}
+	f.confirmNotes()
+	index, root, err := parseResult(&res)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method result: %v", err)
+	}
+	// check the root onchain matches
+	rootOnchain, err := f.GetRoot()
+	if err != nil {
+		return nil, fmt.Errorf("failed to read root onchain: %v", err)
+	}
+	if !bytes.Equal(root, rootOnchain) {
+		return nil, fmt.Errorf("root mismatch: %v != %v", root, rootOnchain)
+	}
+
+	note.InsertedIndex = int(index)
+	f.Tree.AddLeaf(note.Commitment)
+
+	d := &Deposit{
+		FromAddress: from.Address.String(),
+		TxnIds:      res.TxIDs,
+		Round:       res.ConfirmedRound,
+		Note:        note,
+	}
+
+	f.Deposits = append(f.Deposits, d)
+
+	return d, nil
+}
+
+// OptInAsset opts the app in assetId so that it can receive deposits of it,
+// from pays the asset MBR and the transaction fees. Apps with config.CapsEnabled
+// reject ASAs.
+func (f *Frontend) OptInAsset(from *crypto.Account, assetId uint64) error {
+	sp, err := f.algod.SuggestedParams().Do(context.Background())
+	if err != nil {
+		return fmt.Errorf("failed to get suggested params: %v", err)
+	}
+	sp.FlatFee = true
+
+	var atc = transaction.AtomicTransactionComposer{}
+	signer := transaction.BasicAccountTransactionSigner{Account: *from}
+
+	// the payment of the asset MBR, covering also the app call inner transaction fee
+	sp.Fee = 3 * transaction.MinTxnFee
+	txn, err := transaction.MakePaymentTxn(from.Address.String(),
+		crypto.GetApplicationAddress(f.App.Id).String(), config.AssetOptInMbr, nil,
+		types.ZeroAddress.String(), sp,
+	)
+	if err != nil {
+		return fmt.Errorf("failed to make payment txn: %v", err)
+	}
+	err = atc.AddTransaction(transaction.TransactionWithSigner{Txn: txn, Signer: signer})
+	if err != nil {
+		return fmt.Errorf("failed to add payment txn: %v", err)
+	}
+
+	method, err := f.App.Schema.Contract.GetMethodByName(OptInAssetMethod)
+	if err != nil {
+		return fmt.Errorf("failed to get method %s: %v", OptInAssetMethod, err)
+	}
+	sp.Fee = 0
+	txnParams := transaction.AddMethodCallParams{
+		AppID:           f.App.Id,
+		Sender:          from.Address,
+		SuggestedParams: sp,
+		OnComplete:      types.NoOpOC,
+		Signer:          signer,
+		Method:          method,
+		MethodArgs:      []interface{}{assetId},
+	}
+	if err := atc.AddMethodCall(txnParams); err != nil {
+		return fmt.Errorf("failed to add %s method call: %v", OptInAssetMethod, err)
+	}
+
+	if _, err := atc.Execute(f.algod, context.Background(), 4); err != nil {
+		return fmt.Errorf("failed to execute transaction: %v", err)
+	}
+	return nil
+}
@@ -0,0 +1,119 @@
+package client
+
+import (
+	"crypto/sha256"
+	"encoding/binary"
+	"fmt"
+	"io"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"golang.org/x/crypto/hkdf"
+)
+
+// noteDerivationDomain is the HKDF salt of the note secrets derivation
+const noteDerivationDomain = "mithras-note-secrets-v1"
+
+// MinNoteSeedSize is the minimum size of a NoteDerivation seed
+const MinNoteSeedSize = 32
+
+// NoteContext is the transaction context optionally mixed in the derivation of the
+// note secrets, e.g. to separate the notes of two devices sharing a seed. The client
+// does not set it on the transactions it sends, so it guarantees no uniqueness by
+// itself: notes derived from the same seed, index and context share their secrets.
+type NoteContext struct {
+	Sender    types.Address
+	LastValid uint64
+	Lease     [32]byte
+}
+
+// bytes serializes the context as sender || last valid || lease
+func (c *NoteContext) bytes() []byte {
+	b := make([]byte, 0, 32+8+32)
+	b = append(b, c.Sender[:]...)
+	b = binary.BigEndian.AppendUint64(b, c.LastValid)
+	return append(b, c.Lease[:]...)
+}
+
+// NoteDerivation derives the k and r of new notes from a wallet seed instead of
+// random nonces. The secrets of a note are a function of Seed, the note index and
+// Context only: a weak random generator cannot make two notes share a nullifier, and
+// a wallet can re-derive the secrets of its notes from its seed.
+type NoteDerivation struct {
+	Seed []byte
+	// Index is the index following the notes confirmed on chain. The notes of a group
+	// being sent take the next indexes, and Index advances past them once the group
+	// is confirmed; the indexes of a group which fails stay reserved until then, so
+	// that the frontend does not reuse them. Wallets persist Index, e.g. as
+	// wallet.Store.NextNoteIndex, only after the group of the notes is confirmed.
+	Index uint64
+	// Context, if set, is mixed in the secrets of the new notes
+	Context *NoteContext
+
+	// pending is the number of indexes taken since the last confirmed group
+	pending uint64
+}
+
+// next returns the k and r nonces of the next note index not taken yet
+func (d *NoteDerivation) next() (kNonce, rNonce []byte, err error) {
+	kNonce, rNonce, err = deriveNoteNonces(d.Seed, d.Index+d.pending, d.Context)
+	if err != nil {
+		return nil, nil, err
+	}
+	d.pending++
+	return kNonce, rNonce, nil
+}
+
+// confirm advances Index past the indexes taken, once the group of their notes is
+// confirmed
+func (d *NoteDerivation) confirm() {
+	d.Index += d.pending
+	d.pending = 0
+}
+
+// confirmNotes advances the frontend NoteDerivation, if set, after a group creating
+// notes is confirmed
+func (f *Frontend) confirmNotes() {
+	if f.NoteDerivation != nil {
+		f.NoteDerivation.confirm()
+	}
+}
+
+// DeriveNoteSecrets returns the k and r of the note of index derived from seed and
+// the optional context, as new notes do when the frontend NoteDerivation is set
+func (f *Frontend) DeriveNoteSecrets(seed []byte, index uint64, context *NoteContext) (
+	k, r []byte, err error) {
+
+	kNonce, rNonce, err := deriveNoteNonces(seed, index, context)
+	if err != nil {
+		return nil, nil, err
+	}
+	k, r = f.noteKR(kNonce, rNonce)
+	return k, r, nil
+}
+
+// deriveNoteNonces expands seed with HKDF-SHA256, with index and context as info, into
+// two nonces of config.RandomNonceByteSize bytes padded to 32 bytes like random ones
+func deriveNoteNonces(seed []byte, index uint64, context *NoteContext) (kNonce,
+	rNonce []byte, err error) {
+
+	if len(seed) < MinNoteSeedSize {
+		return nil, nil, fmt.Errorf("note seed must be at least %d bytes", MinNoteSeedSize)
+	}
+	info := binary.BigEndian.AppendUint64(nil, index)
+	if context != nil {
+		info = append(info, context.bytes()...)
+	}
+	kdf := hkdf.New(sha256.New, seed, []byte(noteDerivationDomain), info)
+
+	kNonce = make([]byte, 32)
+	rNonce = make([]byte, 32)
+	if _, err := io.ReadFull(kdf, kNonce[32-config.RandomNonceByteSize:]); err != nil {
+		return nil, nil, fmt.Errorf("note secrets derivation failed: %v", err)
+	}
+	if _, err := io.ReadFull(kdf, rNonce[32-config.RandomNonceByteSize:]); err != nil {
+		return nil, nil, fmt.Errorf("note secrets derivation failed: %v", err)
+	}
+	return kNonce, rNonce, nil
+}
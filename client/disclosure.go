@@ -0,0 +1,188 @@
+package client
+
+import (
+	"bytes"
+	"crypto/ed25519"
+	"crypto/sha256"
+	"fmt"
+	"math/big"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"github.com/consensys/gnark-crypto/hash"
+)
+
+// noteOpeningDomain separates the challenges of note openings from other messages
+const noteOpeningDomain = "mithras-note-opening"
+
+// NoteOpening discloses a note to a third party without the spending key: the values
+// committed to, the leaf index of the commitment and its merkle path to Root, a root
+// of the tree. The owner signs the commitment and a challenge chosen by the third
+// party, so that the sender of the note, who also knows its values, cannot claim it.
+// The opening does not reveal whether the note was spent.
+type NoteOpening struct {
+	Amount  uint64
+	AssetId uint64
+	K       []byte
+	R       []byte
+	// OutputX and OutputY are the owner public key coordinates, or the coordinates of
+	// OwnerAddress (see AddressOwnerCoordinates) for notes owned by an address
+	OutputX      []byte
+	OutputY      []byte
+	OwnerAddress string `json:",omitempty"`
+
+	LeafIndex uint64
+	// Path are the sibling hashes from the leaf to Root, excluded
+	Path [][]byte
+	Root []byte
+
+	Challenge []byte
+	// Signature is the signature of the commitment and challenge by the owner key,
+	// eddsa with MiMC, or ed25519 for an address
+	Signature []byte
+}
+
+// OpenNote returns the opening of the inserted note owned by ownerPrivkey against the
+// current root of the frontend tree, signed with challenge by the private key of its
+// ReceivingKey, the note owner
+func (f *Frontend) OpenNote(note *Note, ownerPrivkey *eddsa.PrivateKey, challenge []byte) (
+	*NoteOpening, error) {
+
+	if !note.OwnerAddress.IsZero() {
+		return nil, fmt.Errorf("note owned by an address, see OpenAddressNote")
+	}
+	o, err := f.newNoteOpening(note, challenge)
+	if err != nil {
+		return nil, err
+	}
+	ivk := incomingKey(*ownerPrivkey)
+	o.Signature, err = ivk.Sign(o.message(note.Commitment), hash.MIMC_BLS12_381.New())
+	if err != nil {
+		return nil, fmt.Errorf("failed to sign opening: %v", err)
+	}
+	return o, nil
+}
+
+// OpenAddressNote returns the opening of the inserted note owned by the address of
+// owner against the current root of the frontend tree, signed with challenge
+func (f *Frontend) OpenAddressNote(note *Note, owner *crypto.Account, challenge []byte) (
+	*NoteOpening, error) {
+
+	if note.OwnerAddress != owner.Address {
+		return nil, fmt.Errorf("note not owned by %s", owner.Address)
+	}
+	o, err := f.newNoteOpening(note, challenge)
+	if err != nil {
+		return nil, err
+	}
+	o.OwnerAddress = owner.Address.String()
+	o.Signature, err = crypto.SignBytes(owner.PrivateKey, o.message(note.Commitment))
+	if err != nil {
+		return nil, fmt.Errorf("failed to sign opening: %v", err)
+	}
+	return o, nil
+}
+
+// newNoteOpening returns the unsigned opening of note against the current root
+func (f *Frontend) newNoteOpening(note *Note, challenge []byte) (*NoteOpening, error) {
+	if note.InsertedIndex < 0 {
+		return nil, fmt.Errorf("note not inserted in the tree")
+	}
+	path, err := f.Tree.CreateMerkleProof(f.MakeLeafValue(note), note.InsertedIndex)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create merkle proof: %v", err)
+	}
+	return &NoteOpening{
+		Amount:    note.Amount,
+		AssetId:   note.AssetId,
+		K:         note.K,
+		R:         note.R,
+		OutputX:   note.OutputX,
+		OutputY:   note.OutputY,
+		LeafIndex: uint64(note.InsertedIndex),
+		Path:      path[1:],
+		Root:      f.Tree.Root(),
+		Challenge: challenge,
+	}, nil
+}
+
+// VerifyNoteOpening checks that o opens a commitment included in the tree at its leaf
+// index under its root, one of roots, and is signed by the owner with challenge.
+// roots are the roots the verifier trusts, e.g. the roots of the TreeEvents of a
+// scan or Frontend.RecentRoots. It returns the commitment.
+func (f *Frontend) VerifyNoteOpening(o *NoteOpening, challenge []byte, roots [][]byte) (
+	[]byte, error) {
+
+	if !bytes.Equal(o.Challenge, challenge) {
+		return nil, fmt.Errorf("opening not signed with the challenge")
+	}
+	knownRoot := false
+	for _, root := range roots {
+		knownRoot = knownRoot || bytes.Equal(root, o.Root)
+	}
+	if !knownRoot {
+		return nil, fmt.Errorf("unknown root")
+	}
+	if len(o.Path) != f.Tree.depth {
+		return nil, fmt.Errorf("merkle path of %d hashes, expected %d", len(o.Path),
+			f.Tree.depth)
+	}
+
+	leafValue := f.Tree.hashFunc(uint64ToBytes32(o.Amount), uint64ToBytes32(o.AssetId),
+		o.K, o.R, o.OutputX, o.OutputY)
+	path := append([][]byte{leafValue}, o.Path...)
+	if !f.Tree.Verify(int(o.LeafIndex), path, o.Root) {
+		return nil, fmt.Errorf("commitment not included in the tree at leaf %d", o.LeafIndex)
+	}
+	commitment := f.Tree.hashFunc(leafValue)
+	if err := o.checkSignature(commitment); err != nil {
+		return nil, err
+	}
+	return commitment, nil
+}
+
+// checkSignature checks the opening of commitment is signed by the owner of the note
+func (o *NoteOpening) checkSignature(commitment []byte) error {
+	if o.OwnerAddress != "" {
+		owner, err := types.DecodeAddress(o.OwnerAddress)
+		if err != nil {
+			return fmt.Errorf("invalid owner address: %v", err)
+		}
+		x, y := AddressOwnerCoordinates(owner)
+		if !bytes.Equal(x, o.OutputX) || !bytes.Equal(y, o.OutputY) {
+			return fmt.Errorf("owner address does not match the note coordinates")
+		}
+		if !crypto.VerifyBytes(ed25519.PublicKey(owner[:]), o.message(commitment),
+			o.Signature) {
+			return fmt.Errorf("invalid opening signature")
+		}
+		return nil
+	}
+
+	pubkey := eddsa.PublicKey{}
+	pubkey.A.X.SetBytes(o.OutputX)
+	pubkey.A.Y.SetBytes(o.OutputY)
+	x, y := pubkey.A.X.Bytes(), pubkey.A.Y.Bytes()
+	if !bytes.Equal(x[:], o.OutputX) || !bytes.Equal(y[:], o.OutputY) ||
+		!pubkey.A.IsOnCurve() {
+		return fmt.Errorf("invalid owner public key")
+	}
+	valid, err := pubkey.Verify(o.Signature, o.message(commitment), hash.MIMC_BLS12_381.New())
+	if err != nil || !valid {
+		return fmt.Errorf("invalid opening signature")
+	}
+	return nil
+}
+
+// message returns the message signed by the owner: commitment followed by the
+// challenge hashed with noteOpeningDomain and reduced to a field element, so that
+// both are field elements for MiMC
+func (o *NoteOpening) message(commitment []byte) []byte {
+	digest := sha256.Sum256(append([]byte(noteOpeningDomain), o.Challenge...))
+	challenge := new(big.Int).SetBytes(digest[:])
+	challenge.Mod(challenge, config.Curve.ScalarField())
+	return append(bytes.Clone(commitment), challenge.FillBytes(make([]byte, 32))...)
+}
@@ -0,0 +1,103 @@
+package client
+
+import (
+	"bytes"
+	"crypto/rand"
+	"testing"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+func TestNoteOpening(t *testing.T) {
+	f := newTestFrontend()
+	senderKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	ownerKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for i := range 3 {
+		other, _, err := f.NewNote(uint64(i+1), *senderKey, ReceivingKey(*senderKey))
+		if err != nil {
+			t.Fatal(err)
+		}
+		f.Tree.AddLeaf(other.Commitment)
+	}
+	note, _, err := f.NewNote(1000, *senderKey, ReceivingKey(*ownerKey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	note.InsertedIndex = f.Tree.AddLeaf(note.Commitment)
+	historicalRoot := f.Tree.Root()
+	f.Tree.AddLeaf(note.Commitment) // the tree moves on
+
+	challenge := []byte("auditor nonce 42")
+	opening, err := f.OpenNote(note, ownerKey, challenge)
+	if err != nil {
+		t.Fatal(err)
+	}
+	roots := [][]byte{historicalRoot, f.Tree.Root()}
+	commitment, err := f.VerifyNoteOpening(opening, challenge, roots)
+	if err != nil {
+		t.Fatalf("failed to verify opening: %v", err)
+	}
+	if !bytes.Equal(commitment, note.Commitment) {
+		t.Fatalf("verified commitment does not match")
+	}
+
+	if _, err := f.VerifyNoteOpening(opening, []byte("other nonce"), roots); err == nil {
+		t.Fatalf("expected another challenge to fail")
+	}
+	if _, err := f.VerifyNoteOpening(opening, challenge, roots[:1]); err == nil {
+		t.Fatalf("expected an unknown root to fail")
+	}
+	opening.Amount++
+	if _, err := f.VerifyNoteOpening(opening, challenge, roots); err == nil {
+		t.Fatalf("expected a wrong amount to fail")
+	}
+	opening.Amount--
+
+	// the sender knows the note values but cannot sign as the owner
+	forged, err := f.OpenNote(note, senderKey, challenge)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := f.VerifyNoteOpening(forged, challenge, roots); err == nil {
+		t.Fatalf("expected an opening signed by the sender to fail")
+	}
+}
+
+func TestAddressNoteOpening(t *testing.T) {
+	f := newTestFrontend()
+	owner := crypto.GenerateAccount()
+	notePubkey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	note, _, err := f.NewAddressNote(1000, 0, owner.Address, owner.Address,
+		ReceivingKey(*notePubkey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	note.InsertedIndex = f.Tree.AddLeaf(note.Commitment)
+
+	challenge := []byte("auditor nonce")
+	if _, err := f.OpenAddressNote(note, &crypto.Account{}, challenge); err == nil {
+		t.Fatalf("expected another account to fail")
+	}
+	opening, err := f.OpenAddressNote(note, &owner, challenge)
+	if err != nil {
+		t.Fatal(err)
+	}
+	roots := [][]byte{f.Tree.Root()}
+	if _, err := f.VerifyNoteOpening(opening, challenge, roots); err != nil {
+		t.Fatalf("failed to verify opening: %v", err)
+	}
+	opening.OwnerAddress = crypto.GenerateAccount().Address.String()
+	if _, err := f.VerifyNoteOpening(opening, challenge, roots); err == nil {
+		t.Fatalf("expected another owner address to fail")
+	}
+}
@@ -0,0 +1,105 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// Discovery is the result of a note discovery for a key
+type Discovery struct {
+	// Balances are the sums of the amounts of the unspent notes by asset id,
+	// 0 for Algo
+	Balances map[uint64]uint64
+	// Unspent are the notes owned by the key whose nullifier was not used yet,
+	// in insertion order
+	Unspent []*Note
+	// Spent are the notes owned by the key which have already been spent
+	Spent []*Note
+}
+
+// DiscoverNotes trial-decrypts the encrypted notes of every event with privkey and
+// returns the notes owned by privkey, split between spent and unspent.
+// Events are the ones returned by a Scanner, for a complete discovery they must
+// cover the app history from its creation.
+func (f *Frontend) DiscoverNotes(ctx context.Context, events []*TreeEvent, privkey eddsa.PrivateKey) (
+	*Discovery, error) {
+
+	d := &Discovery{Balances: map[uint64]uint64{}}
+	nk := NewNullifierKey(privkey)
+	ivk := incomingKey(privkey)
+	for _, event := range events {
+		// the encrypted notes are for the inserted commitments in order, i.e., the
+		// deposit, or the change and payment notes of a spend
+		for i, encryptedNote := range event.EncryptedNotes() {
+			if encryptedNote == nil {
+				continue
+			}
+			note, err := f.recoverNote(encryptedNote, ivk, int(event.LeafIndex)+i)
+			if err != nil {
+				continue
+			}
+			// a note decrypting to a different commitment cannot be spent
+			if !bytes.Equal(note.Commitment, event.Commitments[i]) {
+				continue
+			}
+
+			spent, err := f.IsSpent(ctx, note, nk)
+			if err != nil {
+				return nil, err
+			}
+			if spent {
+				d.Spent = append(d.Spent, note)
+				continue
+			}
+			d.Unspent = append(d.Unspent, note)
+			d.Balances[note.AssetId] += note.Amount
+		}
+	}
+	return d, nil
+}
+
+// IsSpent returns true if the nullifier box of the note owned by the holder of nk
+// exists, i.e. the note was spent
+func (f *Frontend) IsSpent(ctx context.Context, note *Note, nk NullifierKey) (bool, error) {
+	return f.NullifierSpent(ctx, f.MakeNullifier(note, nk))
+}
+
+// NullifierSpent returns true if the app has a box named nullifier, i.e. the note of
+// nullifier was spent
+func (f *Frontend) NullifierSpent(ctx context.Context, nullifier []byte) (bool, error) {
+	exists, err := f.boxExists(ctx, nullifier)
+	if err != nil {
+		return false, fmt.Errorf("failed to read nullifier box: %v", err)
+	}
+	return exists, nil
+}
+
+// boxExists returns true if the app has a box named name
+func (f *Frontend) boxExists(ctx context.Context, name []byte) (bool, error) {
+	_, err := f.algod.GetApplicationBoxByName(f.App.Id, name).Do(ctx)
+	if err == nil {
+		return true, nil
+	}
+	if isNotFound(err) {
+		return false, nil
+	}
+	return false, err
+}
+
+// isNotFound returns true if err or an error it wraps is the common.NotFound error
+// the SDK returns for an HTTP 404 response. common.NotFound is declared as an error
+// interface, which any error matches, so each error of the chain is checked for the
+// status the SDK puts first in its message.
+func isNotFound(err error) bool {
+	for ; err != nil; err = errors.Unwrap(err) {
+		if strings.HasPrefix(err.Error(), "HTTP 404") {
+			return true
+		}
+	}
+	return false
+}
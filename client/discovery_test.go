@@ -0,0 +1,27 @@
+package client
+
+import (
+	"errors"
+	"fmt"
+	"testing"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/common"
+)
+
+func TestIsNotFound(t *testing.T) {
+	notFound := common.NotFound(fmt.Errorf("HTTP 404 Not Found: box not found"))
+	if !isNotFound(notFound) {
+		t.Fatal("404 response not detected")
+	}
+	if !isNotFound(fmt.Errorf("failed to read box: %w", notFound)) {
+		t.Fatal("wrapped 404 response not detected")
+	}
+	for _, err := range []error{
+		common.InternalError(fmt.Errorf("HTTP 500 Internal Server Error: round 404")),
+		errors.New("dial tcp: lookup 404.example: no such host"),
+	} {
+		if isNotFound(err) {
+			t.Fatalf("%q detected as not found", err)
+		}
+	}
+}
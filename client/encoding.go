@@ -0,0 +1,304 @@
+package client
+
+import (
+	"bytes"
+	"encoding/binary"
+	"errors"
+	"fmt"
+)
+
+/*
+
+Wire format of an EncryptedNote, as carried in the transaction note field.
+
+Versioned notes start with a 4 bytes header followed by a list of fields:
+
+  magic       : 2 bytes, "MN"
+  version     : 1 byte
+  field count : 1 byte
+  fields      : field count times
+      tag     : 1 byte
+      length  : 2 bytes, big endian
+      value   : length bytes
+
+Fields can appear in any order but each tag at most once. Parsers skip the tags
+they do not know, so new optional fields can be added without a version change; a
+new version is needed only to change or remove required fields.
+
+Version 1 derives the ECIES key once with HKDF-SHA256 (see encrypt.ECIESHKDFKey)
+and seals all the note secrets in a single XChaCha20-Poly1305 ciphertext,
+authenticating magic || version || ephemeral public key. It has these required
+fields:
+
+  0x01 ephemeral public key (compressed point, 32 bytes)
+  0x02 payload: nonce (24 bytes) || ciphertext of
+       amount (8 bytes big endian) || asset id (8 bytes big endian, 0 for Algo) ||
+       flags (1 byte) || k || r || output || input (32 bytes each) || tag (16 bytes)
+
+Bit 0 of the flags is set if the output is the 32 bytes Algorand address owning the
+note instead of a compressed public key, and bit 1 if the input is the Algorand
+address of the sender. A version 1 note is 227 bytes, well within the 1 KB
+transaction note field.
+
+Optional fields:
+
+  0x03 view: ephemeral public key (32 bytes) || payload, the note secrets sealed
+       to a view key as in the payload (see viewkey.go)
+
+Legacy notes, written before the versioned format, have no header and are the
+concatenation of the ephemeral public key and of the ECIES ciphertexts (nonce ||
+secretbox, 72 bytes each, see encrypt.ECIESEncrypt) of the output public key, the
+input public key, the amount (32 bytes big endian), k and r, 392 bytes in total.
+They are encrypted to the spending key of the owner and their commitment has no
+asset id (see RecoverNote).
+
+Transactions inserting several commitments (e.g., transfers) carry several versioned
+notes back to back in the note field, in the order of the inserted commitments. Since
+the versioned layout is self-delimiting no additional framing is needed, and a single
+note is a list of one note.
+*/
+
+const (
+	// EncryptedNoteLegacy is the version of notes with the unversioned layout
+	EncryptedNoteLegacy byte = 0
+	// EncryptedNoteVersion1 is the versioned layout with a single ciphertext
+	EncryptedNoteVersion1 byte = 1
+)
+
+const (
+	tagEphemeralPubkey byte = 0x01
+	tagPayload         byte = 0x02
+	tagView            byte = 0x03
+)
+
+// noteMagic is the prefix of versioned encrypted notes
+var noteMagic = []byte("MN")
+
+const noteHeaderSize = 4 // magic, version, field count
+
+// The sizes of the EncryptedNote fields: the ephemeral public key is a compressed
+// point, the legacy fields are ECIES ciphertexts (nonce || secretbox of 32 bytes),
+// the payload is nonce || ciphertext of the secrets || tag
+const (
+	ephemeralPubkeySize     = 32
+	eciesCiphertextSize     = 24 + 16 + 32
+	legacyEncryptedNoteSize = ephemeralPubkeySize + 5*eciesCiphertextSize
+	payloadSize             = 24 + noteSecretsSize + 16
+	viewFieldSize           = ephemeralPubkeySize + payloadSize
+)
+
+// ErrInvalidEncryptedNote is returned when parsing a malformed encrypted note
+var ErrInvalidEncryptedNote = errors.New("invalid encrypted note")
+
+// Bytes serializes the note in the wire format of its version
+func (n *EncryptedNote) Bytes() []byte {
+	if n.Version == EncryptedNoteLegacy {
+		return n.legacyBytes()
+	}
+	fields := n.fields()
+	b := make([]byte, 0, noteHeaderSize+3*len(fields)+ephemeralPubkeySize+payloadSize+
+		len(n.View))
+	b = append(b, noteMagic...)
+	b = append(b, n.Version, byte(len(fields)))
+	for _, field := range fields {
+		b = append(b, field.tag)
+		b = binary.BigEndian.AppendUint16(b, uint16(len(field.value)))
+		b = append(b, field.value...)
+	}
+	return b
+}
+
+type noteField struct {
+	tag   byte
+	value []byte
+}
+
+// fields returns the required fields of a versioned note in tag order, followed by
+// the optional fields present
+func (n *EncryptedNote) fields() []noteField {
+	fields := []noteField{
+		{tagEphemeralPubkey, n.EphemeralPubkey},
+		{tagPayload, n.Payload},
+	}
+	if len(n.View) > 0 {
+		fields = append(fields, noteField{tagView, n.View})
+	}
+	return fields
+}
+
+// additionalData returns the data authenticated by the payload of versioned notes
+func (n *EncryptedNote) additionalData() []byte {
+	ad := append([]byte{}, noteMagic...)
+	ad = append(ad, n.Version)
+	return append(ad, n.EphemeralPubkey...)
+}
+
+// legacyBytes serializes the note in the legacy unversioned layout
+func (n *EncryptedNote) legacyBytes() []byte {
+	b := make([]byte, 0, legacyEncryptedNoteSize)
+	for _, value := range [][]byte{n.EphemeralPubkey, n.EncryptedOutput, n.EncryptedInput,
+		n.EncryptedAmount, n.EncryptedK, n.EncryptedR} {
+		b = append(b, value...)
+	}
+	return b
+}
+
+// ParseEncryptedNote parses an encrypted note serialized with Bytes, including
+// legacy unversioned notes
+func ParseEncryptedNote(b []byte) (*EncryptedNote, error) {
+	if hasNoteMagic(b) {
+		n, rest, err := parseVersionedNote(b)
+		if err == nil && len(rest) != 0 {
+			err = fmt.Errorf("%w: %d trailing bytes", ErrInvalidEncryptedNote, len(rest))
+		}
+		if err == nil {
+			return n, nil
+		}
+		// a legacy note starting with the magic by chance
+		if len(b) != legacyEncryptedNoteSize {
+			return nil, err
+		}
+	}
+	return parseLegacyNote(b)
+}
+
+// EncryptedNotesBytes serializes notes back to back, as carried in the note field of
+// transactions inserting several commitments
+func EncryptedNotesBytes(notes ...*EncryptedNote) []byte {
+	var b []byte
+	for _, n := range notes {
+		b = append(b, n.Bytes()...)
+	}
+	return b
+}
+
+// ParseEncryptedNotes parses the notes serialized with EncryptedNotesBytes, or a
+// single note as ParseEncryptedNote
+func ParseEncryptedNotes(b []byte) ([]*EncryptedNote, error) {
+	if n, err := ParseEncryptedNote(b); err == nil {
+		return []*EncryptedNote{n}, nil
+	}
+	var notes []*EncryptedNote
+	for len(b) > 0 {
+		if !hasNoteMagic(b) {
+			return nil, fmt.Errorf("%w: note %d has no header", ErrInvalidEncryptedNote,
+				len(notes))
+		}
+		n, rest, err := parseVersionedNote(b)
+		if err != nil {
+			return nil, err
+		}
+		notes = append(notes, n)
+		b = rest
+	}
+	if len(notes) == 0 {
+		return nil, fmt.Errorf("%w: no notes", ErrInvalidEncryptedNote)
+	}
+	return notes, nil
+}
+
+// ParseSpendNotes parses the note field of a withdrawal or transfer, which spends a
+// note into a change note for the spender (UnspentCommitment) and a payment note for
+// the output key (SpentCommitment), in this order. payment is nil for withdrawals
+// carrying the change note only, i.e. with no amount spent to the output key.
+func ParseSpendNotes(b []byte) (change, payment *EncryptedNote, err error) {
+	notes, err := ParseEncryptedNotes(b)
+	if err != nil {
+		return nil, nil, err
+	}
+	if len(notes) > 2 {
+		return nil, nil, fmt.Errorf("%w: %d notes for a spend", ErrInvalidEncryptedNote,
+			len(notes))
+	}
+	change = notes[0]
+	if len(notes) == 2 {
+		payment = notes[1]
+	}
+	return change, payment, nil
+}
+
+// hasNoteMagic returns true if b starts with the header of a versioned note
+func hasNoteMagic(b []byte) bool {
+	return len(b) >= noteHeaderSize && bytes.Equal(b[:len(noteMagic)], noteMagic)
+}
+
+// parseVersionedNote parses a note with the versioned layout at the start of b and
+// returns the bytes following it
+func parseVersionedNote(b []byte) (*EncryptedNote, []byte, error) {
+	version, count := b[2], int(b[3])
+	if version != EncryptedNoteVersion1 {
+		return nil, nil, fmt.Errorf("%w: unknown version %d", ErrInvalidEncryptedNote,
+			version)
+	}
+	values := make(map[byte][]byte, count)
+	rest := b[noteHeaderSize:]
+	for range count {
+		if len(rest) < 3 {
+			return nil, nil, fmt.Errorf("%w: truncated field header", ErrInvalidEncryptedNote)
+		}
+		tag, length := rest[0], int(binary.BigEndian.Uint16(rest[1:3]))
+		rest = rest[3:]
+		if len(rest) < length {
+			return nil, nil, fmt.Errorf("%w: truncated field 0x%02x", ErrInvalidEncryptedNote,
+				tag)
+		}
+		if _, ok := values[tag]; ok {
+			return nil, nil, fmt.Errorf("%w: duplicate field 0x%02x", ErrInvalidEncryptedNote,
+				tag)
+		}
+		values[tag] = rest[:length]
+		rest = rest[length:]
+	}
+
+	n := &EncryptedNote{Version: version}
+	type requiredField struct {
+		tag  byte
+		dest *[]byte
+		size int
+	}
+	required := []requiredField{
+		{tagEphemeralPubkey, &n.EphemeralPubkey, ephemeralPubkeySize},
+		{tagPayload, &n.Payload, payloadSize},
+	}
+	for _, field := range required {
+		value, ok := values[field.tag]
+		if !ok || len(value) == 0 {
+			return nil, nil, fmt.Errorf("%w: missing field 0x%02x", ErrInvalidEncryptedNote,
+				field.tag)
+		}
+		if len(value) != field.size {
+			return nil, nil, fmt.Errorf("%w: field 0x%02x has length %d, expected %d",
+				ErrInvalidEncryptedNote, field.tag, len(value), field.size)
+		}
+		*field.dest = value
+	}
+
+	if view, ok := values[tagView]; ok {
+		if len(view) != viewFieldSize {
+			return nil, nil, fmt.Errorf("%w: view field has length %d, expected %d",
+				ErrInvalidEncryptedNote, len(view), viewFieldSize)
+		}
+		n.View = view
+	}
+	return n, rest, nil
+}
+
+// parseLegacyNote parses a note with the legacy unversioned layout
+func parseLegacyNote(b []byte) (*EncryptedNote, error) {
+	if len(b) != legacyEncryptedNoteSize {
+		return nil, fmt.Errorf("%w: size %d", ErrInvalidEncryptedNote, len(b))
+	}
+	next := func(size int) []byte {
+		field := b[:size]
+		b = b[size:]
+		return field
+	}
+	n := &EncryptedNote{Version: EncryptedNoteLegacy}
+	n.EphemeralPubkey = next(ephemeralPubkeySize)
+	n.EncryptedOutput = next(eciesCiphertextSize)
+	n.EncryptedInput = next(eciesCiphertextSize)
+	n.EncryptedAmount = next(eciesCiphertextSize)
+	n.EncryptedK = next(eciesCiphertextSize)
+	n.EncryptedR = next(eciesCiphertextSize)
+	return n, nil
+}
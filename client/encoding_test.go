@@ -0,0 +1,198 @@
+package client
+
+import (
+	"bytes"
+	"crypto/rand"
+	"errors"
+	"testing"
+)
+
+func randomEncryptedNote(t *testing.T, version byte) *EncryptedNote {
+	field := func(size int) []byte {
+		b := make([]byte, size)
+		if _, err := rand.Read(b); err != nil {
+			t.Fatal(err)
+		}
+		return b
+	}
+	if version != EncryptedNoteLegacy {
+		return &EncryptedNote{
+			Version:         version,
+			EphemeralPubkey: field(ephemeralPubkeySize),
+			Payload:         field(payloadSize),
+		}
+	}
+	return &EncryptedNote{
+		Version:         version,
+		EphemeralPubkey: field(ephemeralPubkeySize),
+		EncryptedOutput: field(eciesCiphertextSize),
+		EncryptedInput:  field(eciesCiphertextSize),
+		EncryptedAmount: field(eciesCiphertextSize),
+		EncryptedK:      field(eciesCiphertextSize),
+		EncryptedR:      field(eciesCiphertextSize),
+	}
+}
+
+func checkSameNote(t *testing.T, got, expected *EncryptedNote) {
+	if got.Version != expected.Version {
+		t.Fatalf("version mismatch: got %d, expected %d", got.Version, expected.Version)
+	}
+	if !bytes.Equal(got.Bytes(), expected.Bytes()) {
+		t.Fatalf("fields mismatch")
+	}
+}
+
+func TestEncryptedNoteRoundTrip(t *testing.T) {
+	for _, version := range []byte{EncryptedNoteLegacy, EncryptedNoteVersion1} {
+		note := randomEncryptedNote(t, version)
+		b := note.Bytes()
+		if version == EncryptedNoteLegacy && len(b) != legacyEncryptedNoteSize {
+			t.Fatalf("legacy note size %d, expected %d", len(b), legacyEncryptedNoteSize)
+		}
+		parsed, err := ParseEncryptedNote(b)
+		if err != nil {
+			t.Fatalf("failed to parse version %d note: %v", version, err)
+		}
+		checkSameNote(t, parsed, note)
+	}
+}
+
+func TestLegacyNoteStartingWithMagic(t *testing.T) {
+	note := randomEncryptedNote(t, EncryptedNoteLegacy)
+	copy(note.EphemeralPubkey, noteMagic)
+	parsed, err := ParseEncryptedNote(note.Bytes())
+	if err != nil {
+		t.Fatalf("failed to parse legacy note: %v", err)
+	}
+	checkSameNote(t, parsed, note)
+}
+
+func TestEncryptedNoteUnknownFieldIsSkipped(t *testing.T) {
+	note := randomEncryptedNote(t, EncryptedNoteVersion1)
+	b := note.Bytes()
+	b[3]++
+	b = append(b, 0xff, 0x00, 0x02, 0xaa, 0xbb)
+	parsed, err := ParseEncryptedNote(b)
+	if err != nil {
+		t.Fatalf("failed to parse note with unknown field: %v", err)
+	}
+	checkSameNote(t, parsed, note)
+}
+
+func TestEncryptedNoteViewField(t *testing.T) {
+	note := randomEncryptedNote(t, EncryptedNoteVersion1)
+	note.View = make([]byte, viewFieldSize)
+	if _, err := rand.Read(note.View); err != nil {
+		t.Fatal(err)
+	}
+	parsed, err := ParseEncryptedNote(note.Bytes())
+	if err != nil {
+		t.Fatalf("failed to parse note with view field: %v", err)
+	}
+	checkSameNote(t, parsed, note)
+	if !bytes.Equal(parsed.View, note.View) {
+		t.Fatalf("view field mismatch")
+	}
+
+	note.View = note.View[:len(note.View)-1]
+	if _, err := ParseEncryptedNote(note.Bytes()); !errors.Is(err, ErrInvalidEncryptedNote) {
+		t.Fatalf("expected ErrInvalidEncryptedNote for a short view field, got %v", err)
+	}
+}
+
+func TestParseMalformedEncryptedNote(t *testing.T) {
+	valid := randomEncryptedNote(t, EncryptedNoteVersion1).Bytes()
+
+	withDuplicate := append([]byte{}, valid...)
+	withDuplicate[3]++
+	withDuplicate = append(withDuplicate, tagEphemeralPubkey, 0x00, 0x01, 0x00)
+
+	withMissing := append([]byte{}, valid[:len(valid)-(3+payloadSize)]...)
+	withMissing[3]--
+
+	shortPayload := append([]byte{}, valid[:len(valid)-1]...)
+	shortPayload[len(valid)-payloadSize-1]--
+
+	unknownVersion := append([]byte{}, valid...)
+	unknownVersion[2] = 0xff
+
+	cases := map[string][]byte{
+		"empty":           {},
+		"header only":     valid[:noteHeaderSize],
+		"truncated":       valid[:len(valid)-1],
+		"trailing bytes":  append(append([]byte{}, valid...), 0x00),
+		"duplicate field": withDuplicate,
+		"missing field":   withMissing,
+		"short payload":   shortPayload,
+		"unknown version": unknownVersion,
+		"wrong legacy":    make([]byte, legacyEncryptedNoteSize-1),
+	}
+	for name, b := range cases {
+		if _, err := ParseEncryptedNote(b); !errors.Is(err, ErrInvalidEncryptedNote) {
+			t.Fatalf("%s: expected ErrInvalidEncryptedNote, got %v", name, err)
+		}
+	}
+}
+
+func TestEncryptedNotesList(t *testing.T) {
+	change := randomEncryptedNote(t, EncryptedNoteVersion1)
+	sent := randomEncryptedNote(t, EncryptedNoteVersion1)
+	sent.View = make([]byte, viewFieldSize)
+
+	parsed, err := ParseEncryptedNotes(EncryptedNotesBytes(change, sent))
+	if err != nil {
+		t.Fatalf("failed to parse notes: %v", err)
+	}
+	if len(parsed) != 2 {
+		t.Fatalf("parsed %d notes, expected 2", len(parsed))
+	}
+	checkSameNote(t, parsed[0], change)
+	checkSameNote(t, parsed[1], sent)
+
+	// a single note, including a legacy one, is a list of one note
+	legacy := randomEncryptedNote(t, EncryptedNoteLegacy)
+	parsed, err = ParseEncryptedNotes(legacy.Bytes())
+	if err != nil || len(parsed) != 1 {
+		t.Fatalf("failed to parse a single legacy note: %v", err)
+	}
+	checkSameNote(t, parsed[0], legacy)
+
+	// legacy notes cannot be listed, and a truncated list is invalid
+	cases := map[string][]byte{
+		"empty":     {},
+		"legacy":    EncryptedNotesBytes(change, legacy),
+		"truncated": EncryptedNotesBytes(change, sent)[:len(change.Bytes())+noteHeaderSize],
+	}
+	for name, b := range cases {
+		if _, err := ParseEncryptedNotes(b); !errors.Is(err, ErrInvalidEncryptedNote) {
+			t.Fatalf("%s: expected ErrInvalidEncryptedNote, got %v", name, err)
+		}
+	}
+}
+
+func TestParseSpendNotes(t *testing.T) {
+	change := randomEncryptedNote(t, EncryptedNoteVersion1)
+	payment := randomEncryptedNote(t, EncryptedNoteVersion1)
+
+	parsedChange, parsedPayment, err := ParseSpendNotes(EncryptedNotesBytes(change, payment))
+	if err != nil {
+		t.Fatalf("failed to parse spend notes: %v", err)
+	}
+	checkSameNote(t, parsedChange, change)
+	checkSameNote(t, parsedPayment, payment)
+
+	// a withdrawal with no payment carries the change only
+	parsedChange, parsedPayment, err = ParseSpendNotes(change.Bytes())
+	if err != nil {
+		t.Fatalf("failed to parse change note: %v", err)
+	}
+	checkSameNote(t, parsedChange, change)
+	if parsedPayment != nil {
+		t.Fatalf("expected no payment note")
+	}
+
+	_, _, err = ParseSpendNotes(EncryptedNotesBytes(change, payment, payment))
+	if !errors.Is(err, ErrInvalidEncryptedNote) {
+		t.Fatalf("expected ErrInvalidEncryptedNote for 3 notes, got %v", err)
+	}
+}
@@ -0,0 +1,108 @@
+package client
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/common/models"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+)
+
+// logicSigBudget is the opcode budget of each top level transaction for the logicsigs
+// of the group, which pool it
+const logicSigBudget = 20_000
+
+// Estimate is the cost of the transaction group of an app method, measured by
+// simulating the group before sending it
+type Estimate struct {
+	Method string
+	// AppBudget is the opcode budget consumed by the method app call and its inner
+	// transactions, the minimum *_OPCODE_BUDGET_OPUP of the method
+	AppBudget uint64
+	// LogicSigBudget is the opcode budget consumed by the logicsigs of the group,
+	// mostly by the verifier
+	LogicSigBudget uint64
+	// Txns is the number of top level transactions of the group, of which Noops are
+	// noop calls padding the group to pool the logicsigs budget
+	Txns  int
+	Noops int
+	// MinNoops is the minimum number of noop calls padding the group for
+	// LogicSigBudget
+	MinNoops int
+	// InnerTxns is the number of inner transactions of the group, mostly the op-ups of
+	// the method app call
+	InnerTxns int
+	// MinFee is the minimum fee credit of the group with MinNoops noops: the minimum
+	// fee of each top level and inner transaction. MinFee / transaction.MinTxnFee is
+	// the minimum fee multiplier of the method (see config).
+	MinFee uint64
+}
+
+// Estimate returns the estimate of the last group of method simulated by the
+// frontend, nil if none was
+func (f *Frontend) Estimate(method string) *Estimate {
+	f.estimatesMu.Lock()
+	defer f.estimatesMu.Unlock()
+	return f.estimates[method]
+}
+
+// simulate simulates the group of atc, whose first transaction calls method and with
+// noops padding noop calls, and records its estimate
+func (f *Frontend) simulate(atc *transaction.AtomicTransactionComposer, method string,
+	noops int) error {
+
+	res, err := atc.Simulate(context.Background(), f.algod, models.SimulateRequest{})
+	if err != nil {
+		return fmt.Errorf("failed to simulate transaction: %v", err)
+	}
+	if len(res.SimulateResponse.TxnGroups) != 1 {
+		return fmt.Errorf("failed to simulate transaction: %d groups simulated",
+			len(res.SimulateResponse.TxnGroups))
+	}
+	group := res.SimulateResponse.TxnGroups[0]
+	if group.FailureMessage != "" {
+		return fmt.Errorf("failed to simulate transaction: %s", group.FailureMessage)
+	}
+
+	estimate := newEstimate(method, group, noops)
+	f.estimatesMu.Lock()
+	defer f.estimatesMu.Unlock()
+	if f.estimates == nil {
+		f.estimates = map[string]*Estimate{}
+	}
+	f.estimates[method] = estimate
+	return nil
+}
+
+// newEstimate returns the estimate of the simulated group of method, whose first
+// transaction is the method app call, padded with noops noop calls
+func newEstimate(method string, group models.SimulateTransactionGroupResult,
+	noops int) *Estimate {
+
+	e := &Estimate{
+		Method: method,
+		Txns:   len(group.TxnResults),
+		Noops:  noops,
+	}
+	for i, txn := range group.TxnResults {
+		if i == 0 {
+			e.AppBudget = txn.AppBudgetConsumed
+		}
+		e.LogicSigBudget += txn.LogicSigBudgetConsumed
+		e.InnerTxns += countInnerTxns(txn.TxnResult.InnerTxns)
+	}
+
+	needed := int((e.LogicSigBudget + logicSigBudget - 1) / logicSigBudget)
+	e.MinNoops = max(0, needed-(e.Txns-noops))
+	e.MinFee = uint64(e.Txns-noops+e.MinNoops+e.InnerTxns) * transaction.MinTxnFee
+	return e
+}
+
+// countInnerTxns returns the number of inner transactions of txns, recursively
+func countInnerTxns(txns []models.PendingTransactionResponse) int {
+	count := len(txns)
+	for _, txn := range txns {
+		count += countInnerTxns(txn.InnerTxns)
+	}
+	return count
+}
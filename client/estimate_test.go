@@ -0,0 +1,43 @@
+package client
+
+import (
+	"testing"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/common/models"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+)
+
+func TestNewEstimate(t *testing.T) {
+	opUps := make([]models.PendingTransactionResponse, 40)
+	group := models.SimulateTransactionGroupResult{
+		TxnResults: make([]models.SimulateTransactionResult, 10),
+	}
+	// the method call with its op-ups, signed by the verifier
+	group.TxnResults[0] = models.SimulateTransactionResult{
+		AppBudgetConsumed:      30_000,
+		LogicSigBudgetConsumed: 145_000,
+		TxnResult:              models.PendingTransactionResponse{InnerTxns: opUps},
+	}
+	// the fee payment, with a payment to the fee recipient
+	group.TxnResults[1] = models.SimulateTransactionResult{
+		TxnResult: models.PendingTransactionResponse{
+			InnerTxns: []models.PendingTransactionResponse{{}},
+		},
+	}
+	for i := 2; i < len(group.TxnResults); i++ {
+		group.TxnResults[i].LogicSigBudgetConsumed = 100
+	}
+
+	e := newEstimate(WithDrawalMethod, group, 8)
+	if e.AppBudget != 30_000 || e.LogicSigBudget != 145_800 || e.Txns != 10 ||
+		e.InnerTxns != 41 {
+		t.Fatalf("unexpected estimate %+v", e)
+	}
+	// 145_800 needs 8 top level transactions, 6 noops besides the call and payment
+	if e.MinNoops != 6 {
+		t.Fatalf("expected 6 noops, got %d", e.MinNoops)
+	}
+	if e.MinFee != (2+6+41)*transaction.MinTxnFee {
+		t.Fatalf("unexpected min fee %d", e.MinFee)
+	}
+}
@@ -0,0 +1,188 @@
+// Package client is an SDK to interact with the Mithras smart contracts: it creates and
+// recovers notes, makes deposits and withdrawals and mirrors the commitment tree
+package client
+
+import (
+	"bytes"
+	"context"
+	"encoding/base64"
+	"fmt"
+	"sync"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+	"github.com/joe-p/Mithras-Protocol/deployed"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/algod"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+const (
+	DepositMethod           = config.DepositMethodName
+	WithDrawalMethod        = config.WithDrawalMethodName
+	AddressWithdrawalMethod = config.AddressWithdrawalMethodName
+	NoOpMethod              = config.NoOpMethodName
+	TransferMethod          = config.TransferMethodName
+	JoinSplitMethod         = config.JoinSplitMethodName
+	OptInAssetMethod        = config.OptInAssetMethodName
+	SetAdminMethod          = config.SetAdminMethodName
+	AddDepositorMethod      = config.AddDepositorMethodName
+	RemoveDepositorMethod   = config.RemoveDepositorMethodName
+)
+
+type Deposit struct {
+	FromAddress string
+	TxnIds      []string // the deposit app call first
+	Round       uint64   // the round the deposit was confirmed in
+	Note        *Note
+}
+
+type Withdrawal struct {
+	ToAddress string
+	TxnIds    []string
+	Note      *Note // the change note
+	SentNote  *Note // the note of SpendAmount owned by the output key
+}
+
+type Transfer struct {
+	TxnIds   []string
+	Note     *Note // the change note
+	SentNote *Note // the note of the receiver
+}
+
+type JoinSplit struct {
+	TxnIds []string
+	Notes  []*Note // the output notes, in output order
+}
+
+// Frontend is a client for a deployed APP
+type Frontend struct {
+	Tree        *Tree
+	Deposits    []*Deposit
+	Withdrawals []*Withdrawal
+	Transfers   []*Transfer
+	JoinSplits  []*JoinSplit
+	App         *App
+
+	// ViewPubkey, if set, is the view key every new note is also encrypted to,
+	// so that its holder can audit the notes without being able to spend them
+	ViewPubkey *eddsa.PublicKey
+
+	// NoteDerivation, if set, derives the secrets of every new note from a wallet
+	// seed instead of random nonces (see derivation.go)
+	NoteDerivation *NoteDerivation
+
+	// Prover, if set, generates the proofs instead of the frontend, e.g. a
+	// RemoteProver for devices too small to prove
+	Prover Prover
+
+	algod *algod.Client
+
+	// estimates holds the estimate of the last group simulated of each method
+	estimates   map[string]*Estimate
+	estimatesMu sync.Mutex
+}
+
+// NewFrontend creates a new Frontend for the app deployed on network, reading the
+// artefacts from the network deployed directory (deployed/<network>)
+func NewFrontend(network deployed.Network, algodClient *algod.Client) (*Frontend, error) {
+	return NewFrontendFromDir(network.DirPath(), algodClient)
+}
+
+// NewFrontendFromDir creates a new Frontend for the app reading the artefacts
+// exported by setup from dir
+func NewFrontendFromDir(dir string, algodClient *algod.Client) (*Frontend, error) {
+	app, err := ReadApp(dir)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read app artefacts from %s: %v", dir, err)
+	}
+	return &Frontend{
+		Tree:  NewTree(app.TreeConfig),
+		App:   app,
+		algod: algodClient,
+	}, nil
+}
+
+// Algod returns the algod client used by the frontend
+func (f *Frontend) Algod() *algod.Client {
+	return f.algod
+}
+
+// GetRoot reads the current root from the global state of the app
+func (f *Frontend) GetRoot() ([]byte, error) {
+	appInfo, err := f.algod.GetApplicationByID(f.App.Id).Do(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to get app info: %v", err)
+	}
+	for _, kv := range appInfo.Params.GlobalState {
+		k, _ := base64.StdEncoding.DecodeString(kv.Key)
+		if bytes.Equal(k, []byte("root")) {
+			root, err := base64.StdEncoding.DecodeString(kv.Value.Bytes)
+			if err != nil {
+				return nil, fmt.Errorf("error decoding root bytes from b64: %v", err)
+			}
+			return root, nil
+		}
+	}
+	return nil, fmt.Errorf("root not found in global state")
+}
+
+// RecentRoots reads the roots accepted by the app from the `roots` box, the most
+// recent first
+func (f *Frontend) RecentRoots(ctx context.Context) ([][]byte, error) {
+	appInfo, err := f.algod.GetApplicationByID(f.App.Id).Do(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get app info: %v", err)
+	}
+	next, found := uint64(0), false
+	for _, kv := range appInfo.Params.GlobalState {
+		k, _ := base64.StdEncoding.DecodeString(kv.Key)
+		if bytes.Equal(k, []byte("next_root_index")) {
+			next, found = kv.Value.Uint, true
+		}
+	}
+	if !found || next >= config.RootsCount {
+		return nil, fmt.Errorf("invalid next root index in global state")
+	}
+
+	box, err := f.algod.GetApplicationBoxByName(f.App.Id, []byte("roots")).Do(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read roots box: %v", err)
+	}
+	if len(box.Value) != 32*config.RootsCount {
+		return nil, fmt.Errorf("unexpected roots box size: %d", len(box.Value))
+	}
+	roots := make([][]byte, config.RootsCount)
+	for i := range roots {
+		j := (int(next) - 1 - i + config.RootsCount) % config.RootsCount
+		roots[i] = box.Value[j*32 : (j+1)*32]
+	}
+	return roots, nil
+}
+
+// parseResult reads the leaf index and root returned by a deposit, withdrawal or
+// transfer
+func parseResult(res *transaction.ExecuteResult) (uint64, []byte, error) {
+	results, ok := res.MethodResults[0].ReturnValue.([]interface{})
+	if !ok {
+		return 0, nil, fmt.Errorf("failed to parse return value")
+	}
+	leafIndex, ok := results[0].(uint64)
+	if !ok {
+		return 0, nil, fmt.Errorf("failed to parse leafIndex")
+	}
+	rootArray, ok := results[1].([]interface{})
+	if !ok {
+		return 0, nil, fmt.Errorf("failed to parse root")
+	}
+	root := []byte{}
+	for _, v := range rootArray {
+		rootByte, ok := v.(uint8)
+		if !ok {
+			return 0, nil, fmt.Errorf("failed to parse root byte")
+		}
+		root = append(root, rootByte)
+	}
+
+	return leafIndex, root[:], nil
+}
@@ -0,0 +1,164 @@
+package client
+
+import (
+	"fmt"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// JoinSplitOutput is a note of Amount owned by Pubkey created by a join-split
+type JoinSplitOutput struct {
+	Amount uint64
+	Pubkey eddsa.PublicKey
+}
+
+type JoinSplitOpts struct {
+	FeeRecipient types.Address
+	FeeSigner    transaction.TransactionSigner
+	Fee          uint64
+	// FromNotes are the circuits.JoinSplitInputs notes to spend, of the same asset
+	// and owned by the spender
+	FromNotes []*Note
+	// Outputs are the circuits.JoinSplitOutputs notes to create, their amounts and
+	// the fee must add up to the amounts of FromNotes
+	Outputs []JoinSplitOutput
+}
+
+// SendJoinSplit spends opts.FromNotes into opts.Outputs and sends the join-split to
+// the network. It consolidates notes, e.g. with an output of the total amount minus
+// the fee and an output of 0 both owned by the spender, or makes a payment larger
+// than any single note, with an output for the receiver and one for the change.
+// If fee is 0 for Algo notes, it is set to the default withdrawal fee plus the MBR
+// of the additional nullifiers; the fee recipient and signer default as in
+// SendWithdrawal. The encrypted notes of the outputs are carried in the note field
+// of the app call, in output order.
+func (f *Frontend) SendJoinSplit(opts *JoinSplitOpts, spenderPrivkey *eddsa.PrivateKey) (
+	*JoinSplit, error) {
+
+	if err := f.App.Supports(JoinSplitMethod); err != nil {
+		return nil, err
+	}
+	if len(opts.FromNotes) != circuits.JoinSplitInputs ||
+		len(opts.Outputs) != circuits.JoinSplitOutputs {
+		return nil, fmt.Errorf("a join-split spends %d notes into %d notes",
+			circuits.JoinSplitInputs, circuits.JoinSplitOutputs)
+	}
+	assetId := opts.FromNotes[0].AssetId
+
+	g, err := f.newSpendGroup(opts.FromNotes[0], circuits.JoinSplitInputs, opts.Fee,
+		opts.FeeRecipient, opts.FeeSigner)
+	if err != nil {
+		return nil, err
+	}
+
+	var inputsTotal, outputsTotal uint64
+	for _, note := range opts.FromNotes {
+		if note.AssetId != assetId {
+			return nil, fmt.Errorf("notes of different assets %d and %d", assetId,
+				note.AssetId)
+		}
+		if inputsTotal+note.Amount < inputsTotal {
+			return nil, fmt.Errorf("inputs total overflows")
+		}
+		inputsTotal += note.Amount
+	}
+	outputsTotal = g.fee
+	for _, output := range opts.Outputs {
+		if outputsTotal+output.Amount < outputsTotal {
+			return nil, fmt.Errorf("outputs total overflows")
+		}
+		outputsTotal += output.Amount
+	}
+	if inputsTotal != outputsTotal {
+		return nil, fmt.Errorf("inputs total %d does not match outputs and fee total %d",
+			inputsTotal, outputsTotal)
+	}
+
+	// every input is proven against the same root, read once
+	root, err := f.GetRoot()
+	if err != nil {
+		return nil, fmt.Errorf("failed to get root: %v", err)
+	}
+	nk := NewNullifierKey(*spenderPrivkey)
+	assignment := &circuits.JoinSplitCircuit{
+		Fee:          g.fee,
+		AssetId:      assetId,
+		Root:         root,
+		NullifierKey: []byte(nk),
+	}
+	for i, note := range opts.FromNotes {
+		index, path, err := f.merklePath(note)
+		if err != nil {
+			return nil, err
+		}
+		nullifier := f.MakeNullifier(note, nk)
+		g.nullifiers = append(g.nullifiers, nullifier)
+
+		assignment.Nullifiers[i] = nullifier
+		assignment.InputAmounts[i] = note.Amount
+		assignment.InputKs[i] = note.K
+		assignment.InputRs[i] = note.R
+		assignment.InputIndexes[i] = index
+		assignment.InputPaths[i] = path
+	}
+
+	outputNotes := make([]*Note, len(opts.Outputs))
+	encryptedNotes := make([]*EncryptedNote, len(opts.Outputs))
+	for i, output := range opts.Outputs {
+		outputNotes[i], encryptedNotes[i], err = f.NewAssetNote(output.Amount, assetId,
+			*spenderPrivkey, output.Pubkey)
+		if err != nil {
+			return nil, fmt.Errorf("failed to create output note %d: %v", i, err)
+		}
+		assignment.Commitments[i] = outputNotes[i].Commitment
+		assignment.OutputAmounts[i] = output.Amount
+		assignment.OutputKs[i] = outputNotes[i].K
+		assignment.OutputRs[i] = outputNotes[i].R
+		assignment.OutputXs[i] = outputNotes[i].OutputX
+		assignment.OutputYs[i] = outputNotes[i].OutputY
+	}
+
+	outputs := [][]byte{uint64ToBytes32(g.fee), uint64ToBytes32(assetId)}
+	for _, note := range outputNotes {
+		outputs = append(outputs, note.Commitment)
+	}
+	assignment.Signature, err = signSpend(spenderPrivkey, outputs...)
+	if err != nil {
+		return nil, err
+	}
+	inputX := spenderPrivkey.PublicKey.A.X.Bytes()
+	inputY := spenderPrivkey.PublicKey.A.Y.Bytes()
+	assignment.SpenderX = inputX[:]
+	assignment.SpenderY = inputY[:]
+
+	g.assignment = assignment
+	g.method = JoinSplitMethod
+	g.verifier = f.App.JoinSplitVerifier
+	g.args = []interface{}{g.feeRecipient[:]}
+	g.accounts = []string{g.feeRecipient.String()}
+	g.note = EncryptedNotesBytes(encryptedNotes...)
+
+	res, firstIndex, err := f.sendSpendGroup(g)
+	if err != nil {
+		return nil, err
+	}
+
+	for i, note := range outputNotes {
+		note.InsertedIndex = int(firstIndex) + i
+		f.Tree.AddLeaf(note.Commitment)
+	}
+
+	j := &JoinSplit{
+		TxnIds: res.TxIDs,
+		Notes:  outputNotes,
+	}
+
+	f.JoinSplits = append(f.JoinSplits, j)
+
+	return j, nil
+}
+
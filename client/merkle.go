@@ -1,4 +1,4 @@
-package test
+package client
 
 import (
 	"bytes"
@@ -7,6 +7,16 @@ import (
 	"github.com/joe-p/Mithras-Protocol/config"
 )
 
+// TreeConfig is the configuration of the commitment tree, as exported by setup
+// in TreeConfig.json
+type TreeConfig struct {
+	Depth      int
+	ZeroValue  []byte
+	ZeroHashes [][]byte
+	HashFunc   config.HashFunc
+}
+
+// Tree is a local mirror of the commitment tree stored by the APP contract
 type Tree struct {
 	subTree    [][]byte
 	zeroHashes [][]byte
@@ -15,9 +25,12 @@ type Tree struct {
 	leafHashes [][]byte
 }
 
+// NewTree returns an empty tree for the given configuration
 func NewTree(c TreeConfig) *Tree {
+	subTree := make([][]byte, len(c.ZeroHashes))
+	copy(subTree, c.ZeroHashes)
 	return &Tree{
-		subTree:    c.ZeroHashes,
+		subTree:    subTree,
 		zeroHashes: c.ZeroHashes,
 		depth:      c.Depth,
 		hashFunc:   c.HashFunc,
@@ -25,21 +38,49 @@ func NewTree(c TreeConfig) *Tree {
 	}
 }
 
-// createMerkleProof returns the Merkle proof for the leaf at the given index.
+// Clone returns a deep copy of the tree
+func (t *Tree) Clone() *Tree {
+	subTree := make([][]byte, len(t.subTree))
+	copy(subTree, t.subTree)
+	leafHashes := make([][]byte, len(t.leafHashes), cap(t.leafHashes))
+	copy(leafHashes, t.leafHashes)
+	return &Tree{
+		subTree:    subTree,
+		zeroHashes: t.zeroHashes,
+		depth:      t.depth,
+		hashFunc:   t.hashFunc,
+		leafHashes: leafHashes,
+	}
+}
+
+// LeafHash returns the hash of the leaf at index, i.e. the note commitment
+func (t *Tree) LeafHash(index int) ([]byte, error) {
+	if index < 0 || index >= len(t.leafHashes) {
+		return nil, errors.New("index out of range")
+	}
+	return t.leafHashes[index], nil
+}
+
+// LeafCount returns the number of leaves inserted in the tree
+func (t *Tree) LeafCount() int {
+	return len(t.leafHashes)
+}
+
+// CreateMerkleProof returns the Merkle proof for the leaf at the given index.
 // The proof is a path that starts with the leaf value (not hashed)
 // and includes the sibling hashes up to but excluding the root.
 // It returns an error if the leaf value does not map to the hash at index.
-func (t *Tree) createMerkleProof(
+func (t *Tree) CreateMerkleProof(
 	leafValue []byte, index int) ([][]byte, error) {
 
 	depth := t.depth
+	if index < 0 || index >= len(t.leafHashes) {
+		return nil, errors.New("index out of range")
+	}
 	leafHash := t.hashFunc(leafValue)
 	if !bytes.Equal(t.leafHashes[index], leafHash) {
 		return nil, errors.New("leaf value does not map to hash at index")
 	}
-	if index >= len(t.leafHashes) {
-		return nil, errors.New("index out of range")
-	}
 
 	proof := make([][]byte, 1, depth+1)
 	proof[0] = leafValue
@@ -49,11 +90,11 @@ func (t *Tree) createMerkleProof(
 	// We can do this by checking the last bit of leaf index:
 	// if it's 0, we are left, if it's 1, we are right.
 	// We rigth shift the index to check the next bit in the next iteration.
-	currentLevel := t.leafHashes
+	currentLevel := make([][]byte, len(t.leafHashes), len(t.leafHashes)+1)
+	copy(currentLevel, t.leafHashes)
 	if len(currentLevel)%2 == 1 {
-		currentLevel = append(currentLevel, t.subTree[0])
+		currentLevel = append(currentLevel, t.zeroHashes[0])
 	}
-	nextLevel := make([][]byte, (len(currentLevel)+1)/2)
 	for i := 0; i < depth; i++ {
 		if index&1 == 0 {
 			proof = append(proof, currentLevel[index+1])
@@ -61,15 +102,15 @@ func (t *Tree) createMerkleProof(
 			proof = append(proof, currentLevel[index-1])
 		}
 
+		nextLevel := make([][]byte, len(currentLevel)/2, len(currentLevel)/2+1)
 		for j := 0; j < len(currentLevel); j += 2 {
 			nextLevel[j/2] = t.hashFunc(currentLevel[j], currentLevel[j+1])
 		}
 		if len(nextLevel)%2 == 1 {
-			nextLevel = append(nextLevel, t.subTree[i+1])
+			nextLevel = append(nextLevel, t.zeroHashes[i+1])
 		}
 
 		currentLevel = nextLevel
-		nextLevel = nextLevel[:len(nextLevel)/2]
 		index >>= 1
 	}
 
@@ -78,7 +119,7 @@ func (t *Tree) createMerkleProof(
 
 // Verify returns true if the leaf at path[0] is included in the tree.
 // path is the proof returned by CreateMerkleProof.
-func (t *Tree) verify(leafIndex int, path [][]byte, root []byte) bool {
+func (t *Tree) Verify(leafIndex int, path [][]byte, root []byte) bool {
 	if len(path) == 0 {
 		return false
 	}
@@ -98,13 +139,13 @@ func (t *Tree) verify(leafIndex int, path [][]byte, root []byte) bool {
 	return bytes.Equal(currentHash, root)
 }
 
-// getRoot returns the root of the tree
-func (t *Tree) getRoot() []byte {
+// Root returns the root of the tree
+func (t *Tree) Root() []byte {
 	return t.subTree[len(t.subTree)-1]
 }
 
-// addLeaf adds a leaf to the tree and returns the index of the leaf
-func (t *Tree) addLeaf(leaf []byte) int {
+// AddLeaf adds a leaf to the tree and returns the index of the leaf
+func (t *Tree) AddLeaf(leaf []byte) int {
 	t.leafHashes = append(t.leafHashes, leaf)
 	currentHash := leaf
 	index := len(t.leafHashes) - 1
@@ -1,6 +1,7 @@
-package test
+package client
 
 import (
+	"crypto/rand"
 	"math"
 	"slices"
 	"testing"
@@ -8,6 +9,7 @@ import (
 	"github.com/joe-p/Mithras-Protocol/config"
 
 	"github.com/consensys/gnark-crypto/ecc"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
 	"github.com/consensys/gnark/frontend"
 	"github.com/consensys/gnark/std/accumulator/merkle"
 	"github.com/consensys/gnark/std/hash/mimc"
@@ -54,26 +56,29 @@ func TestMerkle(t *testing.T) {
 
 	f := Frontend{Tree: tree}
 
-	privkey, err := generateTestKeyPair()
+	privkey, err := eddsa.GenerateKey(rand.Reader)
 	if err != nil {
 		t.Fatal(err)
 	}
 
-	note, _ := f.NewNote(uint64(100), *privkey, privkey.PublicKey)
-	index := tree.addLeaf(note.commitment)
-	note.insertedIndex = index
+	note, _, err := f.NewNote(uint64(100), *privkey, ReceivingKey(*privkey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	index := tree.AddLeaf(note.Commitment)
+	note.InsertedIndex = index
 
-	path, err := tree.createMerkleProof(f.MakeLeafValue(note), index)
+	path, err := tree.CreateMerkleProof(f.MakeLeafValue(note), index)
 	if err != nil {
 		t.Fatal(err)
 	}
 
 	root := tree.ComputeRootFromLeaves()
-	if !slices.Equal(root, tree.getRoot()) {
+	if !slices.Equal(root, tree.Root()) {
 		t.Fatal("Root not computed correctly")
 	}
 
-	if !tree.verify(index, path, root) {
+	if !tree.Verify(index, path, root) {
 		t.Fatal("Merkle proof verification failed")
 	}
 
@@ -99,7 +104,7 @@ func (t *Tree) ComputeRootFromLeaves() []byte {
 	currentLevel := make([][]byte, int(leafCount))
 	copy(currentLevel, t.leafHashes)
 	for i := len(t.leafHashes); i < len(currentLevel); i++ {
-		currentLevel[i] = t.subTree[0]
+		currentLevel[i] = t.zeroHashes[0]
 	}
 	for level := 0; level < t.depth; level++ {
 		for i := 0; i < len(currentLevel); i += 2 {
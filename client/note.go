@@ -0,0 +1,493 @@
+package client
+
+import (
+	"crypto/rand"
+	"encoding/binary"
+	"fmt"
+	"math/big"
+
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+	"github.com/joe-p/Mithras-Protocol/encrypt"
+)
+
+// Note represent a deposit / change in the merkle tree, where it is stored
+// as Commitment
+type Note struct {
+	Amount        uint64
+	AssetId       uint64 // 0 for Algo
+	Commitment    []byte
+	K             []byte
+	R             []byte
+	OutputX       []byte // public key x coordinate
+	OutputY       []byte // public key y coordinate
+	InsertedIndex int    // -1 if not inserted, leaf index in tree otherwise
+
+	// OwnerAddress is set for notes owned by an Algorand address instead of a public
+	// key, OutputX and OutputY are then given by AddressOwnerCoordinates
+	OwnerAddress types.Address
+}
+
+// EncryptedNote holds the secrets of a Note encrypted with ECIES to the note owner,
+// it is carried in the note field of the transaction inserting the note in the tree
+// serialized with Bytes (see encoding.go for the wire format)
+type EncryptedNote struct {
+	Version         byte
+	EncryptedK      []byte
+	EncryptedR      []byte
+	EncryptedOutput []byte
+	EncryptedInput  []byte
+	EncryptedAmount []byte
+	EphemeralPubkey []byte
+
+	// Payload is the single ciphertext of versioned notes, which replaces
+	// the Encrypted* fields
+	Payload []byte
+
+	// View optionally holds the note secrets encrypted to a view key (see viewkey.go)
+	View []byte
+}
+
+// MakeNullifier returns the nullifier of the inserted note owned by the holder of nk,
+// hash(nk, index, k) (see circuits/nullifier.go). nk is ignored for notes owned by an
+// address, whose nullifier key is AddressNullifierKey.
+func (f *Frontend) MakeNullifier(note *Note, nk NullifierKey) []byte {
+	index := uint64ToBytes32(uint64(note.InsertedIndex))
+	return f.Tree.hashFunc(ownerNullifierKey(note, nk), index, note.K)
+}
+
+// MakeLeafValue returns the value of the note leaf in the tree, whose hash is
+// the note commitment
+func (f *Frontend) MakeLeafValue(n *Note) []byte {
+	ab := uint64ToBytes32(n.Amount)
+	asset := uint64ToBytes32(n.AssetId)
+	h := f.Tree.hashFunc(ab, asset, n.K, n.R, n.OutputX, n.OutputY)
+	return h
+}
+
+// MakeCommitment returns the commitment of a note,
+// hash(hash(amount, assetId, k, r, pubkey.X, pubkey.Y))
+func (f *Frontend) MakeCommitment(amount, assetId uint64, k, r []byte, pubkey eddsa.PublicKey) []byte {
+	x := pubkey.A.X.Bytes()
+	y := pubkey.A.Y.Bytes()
+	return f.makeCommitment(amount, assetId, k, r, x[:], y[:])
+}
+
+// MakeAddressCommitment returns the commitment of a note owned by an Algorand address,
+// hash(hash(amount, assetId, k, r, AddressOwnerCoordinates(address)))
+func (f *Frontend) MakeAddressCommitment(amount, assetId uint64, k, r []byte, address types.Address) []byte {
+	x, y := AddressOwnerCoordinates(address)
+	return f.makeCommitment(amount, assetId, k, r, x, y)
+}
+
+// makeCommitment returns the commitment of a note owned by (x, y)
+func (f *Frontend) makeCommitment(amount, assetId uint64, k, r, x, y []byte) []byte {
+	ab := uint64ToBytes32(amount)
+	asset := uint64ToBytes32(assetId)
+
+	h := f.Tree.hashFunc(ab, asset, k, r, x, y)
+	h = f.Tree.hashFunc(h)
+	return h
+}
+
+// makeLegacyCommitment returns the commitment of a legacy note owned by (x, y),
+// hash(hash(amount, k, r, x, y))
+func (f *Frontend) makeLegacyCommitment(amount uint64, k, r, x, y []byte) []byte {
+	h := f.Tree.hashFunc(uint64ToBytes32(amount), k, r, x, y)
+	return f.Tree.hashFunc(h)
+}
+
+// AddressOwnerCoordinates returns the owner coordinates committed to for notes owned
+// by address: (0, address mod the curve order). It is not a point of the curve, so
+// no public key can own the same notes
+func AddressOwnerCoordinates(address types.Address) (x, y []byte) {
+	return make([]byte, 32), addressMod(address)
+}
+
+// addressMod returns address mod the curve order, as the contract computes it for the
+// public inputs
+func addressMod(address types.Address) []byte {
+	mod := new(big.Int).SetBytes(address[:])
+	mod.Mod(mod, config.Curve.ScalarField())
+	return mod.FillBytes(make([]byte, 32))
+}
+
+// randomBigInt returns a random big integer bigger than 1 of up to
+// maxBits bits. If maxBits is less than 1, it defaults to 32.
+func randomBigInt(maxBits int64) (*big.Int, error) {
+	if maxBits < 1 {
+		maxBits = 32
+	}
+	var max *big.Int = big.NewInt(0).Exp(big.NewInt(2), big.NewInt(maxBits), nil)
+	for {
+		n, err := rand.Int(rand.Reader, max)
+		if err != nil {
+			return nil, err
+		}
+		if n.Cmp(big.NewInt(2)) > 0 {
+			return n, nil
+		}
+	}
+}
+
+// NewRandomNonce generates a random nonce of RandomNonceByteSize bytes and returns
+// a 32 byte slice padding with zeros as needed
+func NewRandomNonce() ([]byte, error) {
+	n, err := randomBigInt(config.RandomNonceByteSize * 8)
+	if err != nil {
+		return nil, fmt.Errorf("failed to generate random nonce: %v", err)
+	}
+	res := make([]byte, 32)
+	n.FillBytes(res)
+	return res, nil
+}
+
+// NewNote creates a new Algo note of amount owned by outputPubkey, the ReceivingKey of
+// the owner, and its encrypted version for the owner, and for the frontend ViewPubkey
+// if set. The note records the ReceivingKey of inputPrivKey as its sender.
+func (f *Frontend) NewNote(amount uint64, inputPrivKey eddsa.PrivateKey, outputPubkey eddsa.PublicKey) (*Note, *EncryptedNote, error) {
+	return f.NewAssetNote(amount, 0, inputPrivKey, outputPubkey)
+}
+
+// NewAssetNote creates a new note of amount of assetId (0 for Algo) owned by
+// outputPubkey and its encrypted version, as NewNote
+func (f *Frontend) NewAssetNote(amount, assetId uint64, inputPrivKey eddsa.PrivateKey,
+	outputPubkey eddsa.PublicKey) (*Note, *EncryptedNote, error) {
+
+	return f.newNote(&noteSecrets{
+		amount:       amount,
+		assetId:      assetId,
+		outputPubkey: outputPubkey.Bytes(),
+		inputPubkey:  ReceivingKey(inputPrivKey).Bytes(),
+	}, outputPubkey)
+}
+
+// NewAddressNote creates a new note of amount of assetId (0 for Algo) created by
+// sender and owned by the Algorand address owner, which spends it with
+// SendAddressWithdrawal. The note is encrypted to notePubkey, the ReceivingKey of the
+// key used by the owner to discover its notes, and to the frontend ViewPubkey if set
+func (f *Frontend) NewAddressNote(amount, assetId uint64, sender, owner types.Address,
+	notePubkey eddsa.PublicKey) (*Note, *EncryptedNote, error) {
+
+	return f.newNote(&noteSecrets{
+		amount:          amount,
+		assetId:         assetId,
+		outputPubkey:    owner[:],
+		inputPubkey:     sender[:],
+		outputIsAddress: true,
+		inputIsAddress:  true,
+	}, notePubkey)
+}
+
+// newNote creates a new note with the secrets, to which it adds k and r, and its
+// encrypted version for encryptionPubkey and for the frontend ViewPubkey if set.
+// k and r are derived with the frontend NoteDerivation if set, otherwise from random
+// nonces.
+func (f *Frontend) newNote(secrets *noteSecrets, encryptionPubkey eddsa.PublicKey) (
+	*Note, *EncryptedNote, error) {
+
+	var kNonce, rNonce []byte
+	var err error
+	if f.NoteDerivation != nil {
+		kNonce, rNonce, err = f.NoteDerivation.next()
+		if err != nil {
+			return nil, nil, err
+		}
+	} else {
+		kNonce, err = NewRandomNonce()
+		if err != nil {
+			return nil, nil, err
+		}
+		rNonce, err = NewRandomNonce()
+		if err != nil {
+			return nil, nil, err
+		}
+	}
+	secrets.k, secrets.r = f.noteKR(kNonce, rNonce)
+
+	note, err := f.noteFromSecrets(secrets, -1)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	// K, R, output, input, amount and asset are sealed together to the encryption
+	// pubkey, and to the frontend view key if set
+	encryptedNote, err := encryptNote(secrets, encryptionPubkey)
+	if err != nil {
+		return nil, nil, err
+	}
+	if f.ViewPubkey != nil {
+		viewNote, err := encryptNote(secrets, *f.ViewPubkey)
+		if err != nil {
+			return nil, nil, fmt.Errorf("failed to encrypt note to view key: %v", err)
+		}
+		encryptedNote.View = append(append([]byte{}, viewNote.EphemeralPubkey...),
+			viewNote.Payload...)
+	}
+
+	return note, encryptedNote, nil
+}
+
+// noteKR returns the k and r of a note from their nonces, hash(nonce, domain)
+func (f *Frontend) noteKR(kNonce, rNonce []byte) (k, r []byte) {
+	const sizeFr = 32
+
+	kDomain := make([]byte, sizeFr)
+	rDomain := make([]byte, sizeFr)
+	kDomain[sizeFr-1] = 'k'
+	rDomain[sizeFr-1] = 'r'
+
+	return f.Tree.hashFunc(kNonce, kDomain), f.Tree.hashFunc(rNonce, rDomain)
+}
+
+// noteSecrets are the note values carried encrypted in an EncryptedNote
+type noteSecrets struct {
+	amount       uint64
+	assetId      uint64 // 0 for legacy notes
+	k            []byte
+	r            []byte
+	outputPubkey []byte // compressed, or the owner address if outputIsAddress
+	inputPubkey  []byte // compressed, or the sender address if inputIsAddress, nil for legacy notes
+
+	// outputIsAddress and inputIsAddress are set for Algorand address owners and
+	// senders
+	outputIsAddress bool
+	inputIsAddress  bool
+
+	// legacy is set for the secrets of legacy notes, whose commitment has no asset id
+	legacy bool
+}
+
+// The flags of the secrets of versioned notes
+const (
+	secretsOutputIsAddress byte = 1 << iota
+	secretsInputIsAddress
+)
+
+// noteSecretsSize is the size of the secrets sealed in the payload of a versioned
+// note: amount, asset id (8 bytes each), flags (1 byte), k, r, output, input (32 bytes
+// each)
+const noteSecretsSize = 8 + 8 + 1 + 4*32
+
+// bytes serializes the secrets for the payload of a versioned note
+func (s *noteSecrets) bytes() []byte {
+	b := make([]byte, 0, noteSecretsSize)
+	b = binary.BigEndian.AppendUint64(b, s.amount)
+	b = binary.BigEndian.AppendUint64(b, s.assetId)
+	var flags byte
+	if s.outputIsAddress {
+		flags |= secretsOutputIsAddress
+	}
+	if s.inputIsAddress {
+		flags |= secretsInputIsAddress
+	}
+	b = append(b, flags)
+	b = append(b, s.k...)
+	b = append(b, s.r...)
+	b = append(b, s.outputPubkey...)
+	b = append(b, s.inputPubkey...)
+	return b
+}
+
+// parseNoteSecrets parses the secrets of the payload of a versioned note
+func parseNoteSecrets(b []byte) (*noteSecrets, error) {
+	if len(b) != noteSecretsSize {
+		return nil, fmt.Errorf("invalid note secrets size: %d", len(b))
+	}
+	flags := b[16]
+	if flags&^(secretsOutputIsAddress|secretsInputIsAddress) != 0 {
+		return nil, fmt.Errorf("invalid note secrets flags: %08b", flags)
+	}
+	return &noteSecrets{
+		amount:          binary.BigEndian.Uint64(b[:8]),
+		assetId:         binary.BigEndian.Uint64(b[8:16]),
+		outputIsAddress: flags&secretsOutputIsAddress != 0,
+		inputIsAddress:  flags&secretsInputIsAddress != 0,
+		k:               b[17:49],
+		r:               b[49:81],
+		outputPubkey:    b[81:113],
+		inputPubkey:     b[113:145],
+	}, nil
+}
+
+// encryptNote seals the note secrets to pubkey in a versioned EncryptedNote:
+// the ECIES key is derived once with HKDF and all secrets are sealed in a single
+// ciphertext
+func encryptNote(secrets *noteSecrets, pubkey eddsa.PublicKey) (*EncryptedNote, error) {
+	// Generate ephemeral key pair for ECIES encryption
+	ephemeralPriv, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		return nil, fmt.Errorf("failed to generate ephemeral key: %v", err)
+	}
+	key, err := encrypt.ECIESHKDFKey(pubkey, ephemeralPriv.PublicKey, *ephemeralPriv)
+	if err != nil {
+		return nil, fmt.Errorf("failed to derive note key: %v", err)
+	}
+	n := &EncryptedNote{
+		Version:         EncryptedNoteVersion1,
+		EphemeralPubkey: ephemeralPriv.PublicKey.Bytes(),
+	}
+	n.Payload, err = encrypt.SealWithKey(key, secrets.bytes(), n.additionalData())
+	if err != nil {
+		return nil, fmt.Errorf("failed to encrypt note: %v", err)
+	}
+	return n, nil
+}
+
+// decryptNote decrypts the secrets of a versioned note with privkey
+func decryptNote(encryptedNote *EncryptedNote, privkey eddsa.PrivateKey) (*noteSecrets, error) {
+	var ephemeralPub eddsa.PublicKey
+	if _, err := ephemeralPub.SetBytes(encryptedNote.EphemeralPubkey); err != nil {
+		return nil, fmt.Errorf("failed to read ephemeral public key: %v", err)
+	}
+
+	switch encryptedNote.Version {
+	case EncryptedNoteLegacy:
+		return nil, fmt.Errorf("legacy notes are encrypted to the spending key")
+	case EncryptedNoteVersion1:
+		key, err := encrypt.ECIESHKDFKey(privkey.PublicKey, ephemeralPub, privkey)
+		if err != nil {
+			return nil, fmt.Errorf("failed to derive note key: %v", err)
+		}
+		plaintext, err := encrypt.OpenWithKey(key, encryptedNote.Payload,
+			encryptedNote.additionalData())
+		if err != nil {
+			return nil, fmt.Errorf("failed to decrypt note: %v", err)
+		}
+		return parseNoteSecrets(plaintext)
+	}
+	return nil, fmt.Errorf("unknown encrypted note version %d", encryptedNote.Version)
+}
+
+// decryptLegacyNote decrypts the secrets of a legacy note, with one ECIES ciphertext
+// per value, with the spending key privkey the note was encrypted to
+func decryptLegacyNote(encryptedNote *EncryptedNote, privkey eddsa.PrivateKey) (
+	*noteSecrets, error) {
+
+	var ephemeralPub eddsa.PublicKey
+	if _, err := ephemeralPub.SetBytes(encryptedNote.EphemeralPubkey); err != nil {
+		return nil, fmt.Errorf("failed to read ephemeral public key: %v", err)
+	}
+
+	// Decrypt k and r using the private key
+	k, err := encrypt.ECIESDecrypt(encryptedNote.EncryptedK, ephemeralPub, privkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt k: %v", err)
+	}
+
+	r, err := encrypt.ECIESDecrypt(encryptedNote.EncryptedR, ephemeralPub, privkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt r: %v", err)
+	}
+
+	// Decrypt the output public key
+	outputPubkeyBytes, err := encrypt.ECIESDecrypt(encryptedNote.EncryptedOutput, ephemeralPub, privkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt output: %v", err)
+	}
+
+	// Decrypt the amount
+	amountBytes, err := encrypt.ECIESDecrypt(encryptedNote.EncryptedAmount, ephemeralPub, privkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt amount: %v", err)
+	}
+	if len(amountBytes) != 32 {
+		return nil, fmt.Errorf("invalid amount length: %d", len(amountBytes))
+	}
+
+	return &noteSecrets{
+		// amount is stored in last 8 bytes of 32-byte array
+		amount:       binary.BigEndian.Uint64(amountBytes[24:]),
+		k:            k,
+		r:            r,
+		outputPubkey: outputPubkeyBytes,
+		legacy:       true,
+	}, nil
+}
+
+// uint64ToBytes32 converts a uint64 to a 32 byte array
+func uint64ToBytes32(amount uint64) []byte {
+	amountBytes := make([]byte, 32)
+	binary.BigEndian.PutUint64(amountBytes[24:], amount)
+	return amountBytes
+}
+
+// RecoverNote attempts to decrypt and reconstruct a note from encrypted data
+// using the provided private key, whose notes are encrypted to its ReceivingKey.
+// Legacy notes, encrypted to the key itself and committed to without asset id by
+// the deployments before versioned notes, are recovered with their legacy commitment;
+// the current circuits cannot spend them. Returns a Note if successful.
+func (f *Frontend) RecoverNote(encryptedNote *EncryptedNote, privkey eddsa.PrivateKey, insertedIndex int) (*Note, error) {
+	if encryptedNote.Version == EncryptedNoteLegacy {
+		secrets, err := decryptLegacyNote(encryptedNote, privkey)
+		if err != nil {
+			return nil, err
+		}
+		return f.noteFromSecrets(secrets, insertedIndex)
+	}
+	return f.recoverNote(encryptedNote, incomingKey(privkey), insertedIndex)
+}
+
+// recoverNote decrypts and reconstructs a note encrypted to the public key of ivk
+func (f *Frontend) recoverNote(encryptedNote *EncryptedNote, ivk eddsa.PrivateKey, insertedIndex int) (*Note, error) {
+	secrets, err := decryptNote(encryptedNote, ivk)
+	if err != nil {
+		return nil, err
+	}
+	return f.noteFromSecrets(secrets, insertedIndex)
+}
+
+// noteFromSecrets reconstructs the note with the decrypted secrets
+func (f *Frontend) noteFromSecrets(secrets *noteSecrets, insertedIndex int) (*Note, error) {
+	note := &Note{
+		Amount:        secrets.amount,
+		AssetId:       secrets.assetId,
+		K:             secrets.k,
+		R:             secrets.r,
+		InsertedIndex: insertedIndex,
+	}
+
+	if secrets.outputIsAddress {
+		if len(secrets.outputPubkey) != len(note.OwnerAddress) {
+			return nil, fmt.Errorf("invalid owner address length: %d",
+				len(secrets.outputPubkey))
+		}
+		copy(note.OwnerAddress[:], secrets.outputPubkey)
+		note.OutputX, note.OutputY = AddressOwnerCoordinates(note.OwnerAddress)
+	} else {
+		// Reconstruct the public key from bytes
+		var outputPubkey eddsa.PublicKey
+		_, err := outputPubkey.SetBytes(secrets.outputPubkey)
+		if err != nil {
+			return nil, fmt.Errorf("failed to reconstruct public key: %v", err)
+		}
+
+		// Extract coordinates
+		outputXCoord := outputPubkey.A.X.Bytes()
+		outputYCoord := outputPubkey.A.Y.Bytes()
+		note.OutputX, note.OutputY = outputXCoord[:], outputYCoord[:]
+	}
+
+	// Compute the commitment to verify correctness
+	if secrets.legacy {
+		note.Commitment = f.makeLegacyCommitment(note.Amount, note.K, note.R,
+			note.OutputX, note.OutputY)
+	} else {
+		note.Commitment = f.makeCommitment(note.Amount, note.AssetId, note.K, note.R,
+			note.OutputX, note.OutputY)
+	}
+
+	return note, nil
+}
+
+// TryRecoverNote attempts to recover a note without returning an error.
+// Returns nil if the note cannot be recovered (e.g., wrong private key).
+func (f *Frontend) TryRecoverNote(encryptedNote *EncryptedNote, privkey eddsa.PrivateKey, insertedIndex int) *Note {
+	note, err := f.RecoverNote(encryptedNote, privkey, insertedIndex)
+	if err != nil {
+		return nil
+	}
+	return note
+}
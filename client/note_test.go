@@ -0,0 +1,347 @@
+package client
+
+import (
+	"bytes"
+	"crypto/rand"
+	"math/big"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+	"github.com/joe-p/Mithras-Protocol/encrypt"
+
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	edwards "github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// newTestFrontend returns a Frontend with a small tree and no app, enough to
+// create and recover notes
+func newTestFrontend() *Frontend {
+	tc := TreeConfig{
+		Depth:     4,
+		ZeroValue: []byte{0},
+		HashFunc:  config.Hash,
+	}
+	tc.ZeroHashes = config.GenerateZeroHashes(tc.Depth, tc.ZeroValue)
+	return &Frontend{Tree: NewTree(tc)}
+}
+
+func TestVersionedNoteRecovery(t *testing.T) {
+	f := newTestFrontend()
+	inputKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	outputKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	note, encryptedNote, err := f.NewNote(1000, *inputKey, ReceivingKey(*outputKey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	b := encryptedNote.Bytes()
+	if encryptedNote.Version != EncryptedNoteVersion1 || len(b) != 227 {
+		t.Fatalf("expected a 227 bytes version 1 note, got version %d of %d bytes",
+			encryptedNote.Version, len(b))
+	}
+
+	parsed, err := ParseEncryptedNote(b)
+	if err != nil {
+		t.Fatal(err)
+	}
+	recovered, err := f.RecoverNote(parsed, *outputKey, 3)
+	if err != nil {
+		t.Fatalf("failed to recover note: %v", err)
+	}
+	if recovered.Amount != note.Amount || !bytes.Equal(recovered.Commitment, note.Commitment) ||
+		recovered.InsertedIndex != 3 {
+		t.Fatalf("recovered note does not match")
+	}
+
+	if f.TryRecoverNote(parsed, *inputKey, 3) != nil {
+		t.Fatalf("recovered note with the wrong key")
+	}
+
+	// tampering with the authenticated header breaks decryption
+	parsed.EphemeralPubkey = inputKey.PublicKey.Bytes()
+	if f.TryRecoverNote(parsed, *outputKey, 3) != nil {
+		t.Fatalf("recovered note with a different ephemeral key")
+	}
+}
+
+func TestLegacyNoteRecovery(t *testing.T) {
+	f := newTestFrontend()
+	inputKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	outputKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	ephemeralKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	k, r := make([]byte, 32), make([]byte, 32)
+	k[31], r[31] = 1, 2
+	amount := uint64(42)
+
+	// legacy notes are encrypted to the spending key and have no asset id
+	pubkey := outputKey.PublicKey
+	legacy := &EncryptedNote{
+		Version:         EncryptedNoteLegacy,
+		EphemeralPubkey: ephemeralKey.PublicKey.Bytes(),
+	}
+	for _, v := range []struct {
+		dest      *[]byte
+		plaintext []byte
+	}{
+		{&legacy.EncryptedOutput, pubkey.Bytes()},
+		{&legacy.EncryptedInput, inputKey.PublicKey.Bytes()},
+		{&legacy.EncryptedAmount, uint64ToBytes32(amount)},
+		{&legacy.EncryptedK, k},
+		{&legacy.EncryptedR, r},
+	} {
+		*v.dest, err = encrypt.ECIESEncrypt(v.plaintext, pubkey, ephemeralKey.PublicKey, *ephemeralKey)
+		if err != nil {
+			t.Fatal(err)
+		}
+	}
+	b := legacy.Bytes()
+	if len(b) != 392 {
+		t.Fatalf("expected a 392 bytes legacy note, got %d bytes", len(b))
+	}
+
+	parsed, err := ParseEncryptedNote(b)
+	if err != nil {
+		t.Fatal(err)
+	}
+	recovered, err := f.RecoverNote(parsed, *outputKey, 0)
+	if err != nil {
+		t.Fatalf("failed to recover legacy note: %v", err)
+	}
+	x, y := pubkey.A.X.Bytes(), pubkey.A.Y.Bytes()
+	commitment := config.Hash(config.Hash(uint64ToBytes32(amount), k, r, x[:], y[:]))
+	if recovered.Amount != amount || recovered.AssetId != 0 ||
+		!bytes.Equal(recovered.Commitment, commitment) {
+		t.Fatalf("recovered legacy note does not match")
+	}
+	if f.TryRecoverNote(parsed, *inputKey, 0) != nil {
+		t.Fatalf("recovered legacy note with a wrong key")
+	}
+}
+
+func TestViewedNoteRecovery(t *testing.T) {
+	f := newTestFrontend()
+	keys := make([]*eddsa.PrivateKey, 3)
+	for i := range keys {
+		var err error
+		if keys[i], err = eddsa.GenerateKey(rand.Reader); err != nil {
+			t.Fatal(err)
+		}
+	}
+	inputKey, outputKey, viewKey := keys[0], keys[1], keys[2]
+
+	// without a view key the note has no view field
+	_, encryptedNote, err := f.NewNote(1000, *inputKey, ReceivingKey(*outputKey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := f.RecoverViewedNote(encryptedNote, *viewKey, 0); err == nil {
+		t.Fatalf("recovered a note without view field")
+	}
+
+	f.ViewPubkey = &viewKey.PublicKey
+	note, encryptedNote, err := f.NewNote(1000, *inputKey, ReceivingKey(*outputKey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	parsed, err := ParseEncryptedNote(encryptedNote.Bytes())
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewed, err := f.RecoverViewedNote(parsed, *viewKey, 5)
+	if err != nil {
+		t.Fatalf("failed to recover viewed note: %v", err)
+	}
+	if viewed.Amount != note.Amount || !bytes.Equal(viewed.Commitment, note.Commitment) ||
+		viewed.InsertedIndex != 5 {
+		t.Fatalf("viewed note does not match")
+	}
+	sender, receiver := ReceivingKey(*inputKey), ReceivingKey(*outputKey)
+	if !viewed.Sender.Equal(&sender) || !viewed.Receiver.Equal(&receiver) {
+		t.Fatalf("viewed note sender or receiver does not match")
+	}
+
+	// the owner still recovers the note, the view key cannot open the owner payload
+	if f.TryRecoverNote(parsed, *outputKey, 5) == nil {
+		t.Fatalf("owner failed to recover a note with view field")
+	}
+	if f.TryRecoverNote(parsed, *viewKey, 5) != nil {
+		t.Fatalf("recovered the owner payload with the view key")
+	}
+	if _, err := f.RecoverViewedNote(parsed, *outputKey, 5); err == nil {
+		t.Fatalf("recovered the view field with the owner key")
+	}
+}
+
+func TestAddressNoteRecovery(t *testing.T) {
+	f := newTestFrontend()
+	noteKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var sender, owner types.Address
+	sender[0], owner[0] = 1, 2
+
+	note, encryptedNote, err := f.NewAddressNote(1000, 7, sender, owner, ReceivingKey(*noteKey))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.OwnerAddress != owner ||
+		!bytes.Equal(note.Commitment, f.MakeAddressCommitment(1000, 7, note.K, note.R, owner)) {
+		t.Fatalf("address note does not commit to its owner")
+	}
+
+	recovered, err := f.RecoverNote(encryptedNote, *noteKey, 0)
+	if err != nil {
+		t.Fatalf("failed to recover address note: %v", err)
+	}
+	if recovered.OwnerAddress != owner || recovered.AssetId != 7 ||
+		!bytes.Equal(recovered.Commitment, note.Commitment) {
+		t.Fatalf("recovered address note does not match")
+	}
+
+	// the owner coordinates are not a point of the curve, no key commits to them
+	x, y := AddressOwnerCoordinates(owner)
+	if !bytes.Equal(x, make([]byte, 32)) || len(y) != 32 {
+		t.Fatalf("unexpected owner coordinates")
+	}
+	if bytes.Equal(note.Commitment, f.MakeCommitment(1000, 7, note.K, note.R, ReceivingKey(*noteKey))) {
+		t.Fatalf("address note commits to the note key")
+	}
+}
+
+func TestDerivedNoteSecrets(t *testing.T) {
+	f := newTestFrontend()
+	key, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	seed := make([]byte, MinNoteSeedSize)
+	if _, err := rand.Read(seed); err != nil {
+		t.Fatal(err)
+	}
+
+	f.NoteDerivation = &NoteDerivation{Seed: seed, Index: 7}
+	first, _, err := f.NewNote(1000, *key, ReceivingKey(*key))
+	if err != nil {
+		t.Fatal(err)
+	}
+	second, _, err := f.NewNote(1000, *key, ReceivingKey(*key))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if bytes.Equal(first.K, second.K) || bytes.Equal(first.R, second.R) {
+		t.Fatalf("expected two notes with different secrets")
+	}
+	// the index advances only once the notes are confirmed
+	if f.NoteDerivation.Index != 7 {
+		t.Fatalf("index advanced to %d before confirmation", f.NoteDerivation.Index)
+	}
+	f.confirmNotes()
+	if f.NoteDerivation.Index != 9 {
+		t.Fatalf("expected index 9 after confirmation, got %d", f.NoteDerivation.Index)
+	}
+
+	// the wallet re-derives the secrets of its notes from the seed
+	k, r, err := f.DeriveNoteSecrets(seed, 7, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !bytes.Equal(k, first.K) || !bytes.Equal(r, first.R) {
+		t.Fatalf("re-derived secrets do not match the note")
+	}
+
+	// the context changes the secrets of the same index
+	context := &NoteContext{Sender: types.Address{1}, LastValid: 1000}
+	kContext, _, err := f.DeriveNoteSecrets(seed, 7, context)
+	if err != nil {
+		t.Fatal(err)
+	}
+	context.Lease[0] = 1
+	kLease, _, err := f.DeriveNoteSecrets(seed, 7, context)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if bytes.Equal(kContext, k) || bytes.Equal(kLease, kContext) {
+		t.Fatalf("expected the context to change the secrets")
+	}
+
+	if _, _, err := f.DeriveNoteSecrets(seed[:MinNoteSeedSize-1], 0, nil); err == nil {
+		t.Fatalf("expected a short seed to be rejected")
+	}
+}
+
+func TestNullifierBinding(t *testing.T) {
+	f := newTestFrontend()
+	key, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// the receiving key is hash(A, nk)*Base as in the circuits, which multiply by the
+	// hash without reducing it
+	nk := NewNullifierKey(*key)
+	x, y := key.PublicKey.A.X.Bytes(), key.PublicKey.A.Y.Bytes()
+	var scalar big.Int
+	scalar.SetBytes(config.Hash(x[:], y[:], nk))
+	var receivingKey edwards.PointAffine
+	receivingKey.ScalarMultiplication(&edwards.GetEdwardsCurve().Base, &scalar)
+	owner := ReceivingKey(*key)
+	if !receivingKey.Equal(&owner.A) || owner.A.Equal(&key.PublicKey.A) {
+		t.Fatalf("receiving key does not commit to the nullifier key")
+	}
+	ivk := incomingKey(*key)
+	if !ivk.PublicKey.Equal(&owner) {
+		t.Fatalf("incoming key does not match the receiving key")
+	}
+
+	note, _, err := f.NewNote(1000, *key, owner)
+	if err != nil {
+		t.Fatal(err)
+	}
+	note.InsertedIndex = 1
+	nullifier := f.MakeNullifier(note, nk)
+
+	// the same k and amount at another index, or with another key, give another
+	// nullifier
+	sameSecrets := *note
+	sameSecrets.InsertedIndex = 2
+	if bytes.Equal(f.MakeNullifier(&sameSecrets, nk), nullifier) {
+		t.Fatalf("expected the nullifier to bind the leaf index")
+	}
+	if bytes.Equal(f.MakeNullifier(note, NewNullifierKey(*otherKey)), nullifier) {
+		t.Fatalf("expected the nullifier to bind the nullifier key")
+	}
+
+	// address notes always use the address nullifier key
+	address := types.Address{1}
+	addressNote, _, err := f.NewAddressNote(1000, 0, address, address, owner)
+	if err != nil {
+		t.Fatal(err)
+	}
+	addressNote.InsertedIndex = 1
+	if !bytes.Equal(f.MakeNullifier(addressNote, nk), f.MakeNullifier(addressNote, nil)) ||
+		!bytes.Equal(f.MakeNullifier(addressNote, nil),
+			config.Hash(AddressNullifierKey(address), uint64ToBytes32(1), addressNote.K)) {
+		t.Fatalf("expected the address nullifier key for address notes")
+	}
+}
@@ -0,0 +1,96 @@
+package client
+
+import (
+	"crypto/sha256"
+	"math/big"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	edwards "github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// NullifierKey is the secret of a note owner bound to the nullifiers of its notes
+// (see circuits/nullifier.go). It tells whether the notes were spent but cannot spend
+// them without a signature of the owner, so the owner can share it with an auditor
+// along with a view key.
+type NullifierKey []byte
+
+// NewNullifierKey returns the nullifier key of the notes owned by the ReceivingKey of
+// privkey
+func NewNullifierKey(privkey eddsa.PrivateKey) NullifierKey {
+	return config.Hash(nullifierScalar(privkey), nullifierKeyDomain())
+}
+
+// AddressNullifierKey returns the nullifier key of the notes owned by address. An
+// address has no secret to derive it from, so anyone knowing the owner of a note,
+// such as its creator, can compute its nullifier.
+func AddressNullifierKey(address types.Address) NullifierKey {
+	_, y := AddressOwnerCoordinates(address)
+	return config.Hash(y, nullifierKeyDomain())
+}
+
+// ReceivingKey returns the public key owning the notes of privkey,
+// hash(privkey.PublicKey, NewNullifierKey(privkey))*Base. It is the key notes are
+// created for and encrypted to: spending them proves its nullifier key and a signature
+// by privkey, never the scalar of privkey (see circuits/nullifier.go).
+func ReceivingKey(privkey eddsa.PrivateKey) eddsa.PublicKey {
+	return incomingKey(privkey).PublicKey
+}
+
+// incomingKey returns the private key of ReceivingKey(privkey), which decrypts the
+// notes of privkey. Its scalar is hash(A.X, A.Y, nk) mod the order of the curve
+// subgroup, where A is the public key of privkey and nk its nullifier key.
+func incomingKey(privkey eddsa.PrivateKey) eddsa.PrivateKey {
+	const sizeFr = 32
+	x := privkey.PublicKey.A.X.Bytes()
+	y := privkey.PublicKey.A.Y.Bytes()
+	scalar := new(big.Int).SetBytes(config.Hash(x[:], y[:], NewNullifierKey(privkey)))
+	curve := edwards.GetEdwardsCurve()
+	scalar.Mod(scalar, &curve.Order)
+
+	var pubkey eddsa.PublicKey
+	pubkey.A.ScalarMultiplication(&curve.Base, scalar)
+	scalarBytes := scalar.FillBytes(make([]byte, sizeFr))
+	// the signing nonces of the key are derived from randSrc, hash(scalar)
+	randSrc := sha256.Sum256(scalarBytes)
+
+	var key eddsa.PrivateKey
+	b := append(append(pubkey.Bytes(), scalarBytes...), randSrc[:]...)
+	if _, err := key.SetBytes(b); err != nil {
+		// the public key is on the curve and b has the size of a private key
+		panic(err)
+	}
+	return key
+}
+
+// ownerNullifierKey returns the nullifier key of the address owner of note, or nk if
+// note is owned by a public key
+func ownerNullifierKey(note *Note, nk NullifierKey) NullifierKey {
+	if !note.OwnerAddress.IsZero() {
+		return AddressNullifierKey(note.OwnerAddress)
+	}
+	return nk
+}
+
+// nullifierKeyDomain returns circuits.NullifierKeyDomain as a field element
+func nullifierKeyDomain() []byte {
+	domain := make([]byte, 32)
+	domain[31] = circuits.NullifierKeyDomain
+	return domain
+}
+
+// nullifierScalar returns the private scalar of privkey mod the order of the curve
+// subgroup, so that it fits a field element. It is only hashed off circuit, the
+// circuits take the nullifier key instead.
+func nullifierScalar(privkey eddsa.PrivateKey) []byte {
+	const pubSize = 32
+	const sizeFr = 32
+	privBytes := privkey.Bytes()
+	scalar := new(big.Int).SetBytes(privBytes[pubSize : pubSize+sizeFr])
+	curve := edwards.GetEdwardsCurve()
+	scalar.Mod(scalar, &curve.Order)
+	return scalar.FillBytes(make([]byte, sizeFr))
+}
@@ -0,0 +1,134 @@
+package client
+
+import (
+	"errors"
+	"fmt"
+	"math/bits"
+	"sort"
+
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// ErrInsufficientFunds is returned when the notes cannot pay the amount and the fees
+var ErrInsufficientFunds = errors.New("insufficient funds")
+
+// PlannedWithdrawal is a withdrawal of a plan, with the options of SendWithdrawal
+type PlannedWithdrawal struct {
+	FromNote *Note
+	Amount   uint64 // withdrawn to the recipient
+	Fee      uint64
+	Change   uint64 // kept in the change note
+	// NoChange is set when there is no change, so that no empty note is added to
+	// the tree
+	NoChange bool
+}
+
+// WithdrawalPlan is a sequence of withdrawals of notes of AssetId paying Amount
+type WithdrawalPlan struct {
+	AssetId     uint64
+	Amount      uint64
+	Fees        uint64
+	Withdrawals []*PlannedWithdrawal
+}
+
+// DefaultWithdrawalFee returns the fee of a withdrawal of assetId when none is given,
+// the transaction fees and the nullifier MBR for Algo, 0 for ASAs whose fee recipient
+// pays them in Algo
+func DefaultWithdrawalFee(assetId uint64) uint64 {
+	if assetId != 0 {
+		return 0
+	}
+	return defaultSpendFee(1)
+}
+
+// PlanWithdrawal selects among notes the notes of assetId to withdraw amount and
+// returns the withdrawals to send, each paying fee, or DefaultWithdrawalFee if fee is
+// 0. The fee of Algo withdrawals must cover the nullifier MBR and the transaction
+// fee, as SendWithdrawal requires. A single note is used if one can pay amount and
+// its fee, the smallest of them to keep large notes whole; otherwise the largest notes
+// are withdrawn in full until the last one pays the rest and keeps the change. It
+// returns ErrInsufficientFunds if the notes cannot pay amount and the fees.
+func PlanWithdrawal(notes []*Note, assetId, amount, fee uint64) (*WithdrawalPlan, error) {
+	if amount == 0 {
+		return nil, fmt.Errorf("nothing to withdraw")
+	}
+	if fee == 0 {
+		fee = DefaultWithdrawalFee(assetId)
+	}
+	if err := checkSpendFee(assetId, 1, fee); err != nil {
+		return nil, err
+	}
+
+	// candidates are the inserted notes of the asset worth more than the fee to spend
+	var candidates []*Note
+	for _, note := range notes {
+		if note.AssetId == assetId && note.InsertedIndex >= 0 && note.Amount > fee {
+			candidates = append(candidates, note)
+		}
+	}
+	sort.SliceStable(candidates, func(i, j int) bool {
+		return candidates[i].Amount < candidates[j].Amount
+	})
+
+	plan := &WithdrawalPlan{AssetId: assetId, Amount: amount}
+	needed, carry := bits.Add64(amount, fee, 0)
+	for _, note := range candidates {
+		if carry == 0 && note.Amount >= needed {
+			plan.add(note, amount, fee)
+			return plan, nil
+		}
+	}
+
+	remaining := amount
+	for i := len(candidates) - 1; i >= 0 && remaining > 0; i-- {
+		note := candidates[i]
+		withdrawal := min(note.Amount-fee, remaining)
+		plan.add(note, withdrawal, fee)
+		remaining -= withdrawal
+	}
+	if remaining > 0 {
+		return nil, fmt.Errorf("%w: %d of asset %d missing to withdraw %d with %d fees",
+			ErrInsufficientFunds, remaining, assetId, amount, plan.Fees)
+	}
+	return plan, nil
+}
+
+// add adds the withdrawal of amount from note paying fee, amount + fee <= note.Amount
+func (p *WithdrawalPlan) add(note *Note, amount, fee uint64) {
+	change := note.Amount - amount - fee
+	p.Fees += fee
+	p.Withdrawals = append(p.Withdrawals, &PlannedWithdrawal{
+		FromNote: note,
+		Amount:   amount,
+		Fee:      fee,
+		Change:   change,
+		NoChange: change == 0,
+	})
+}
+
+// SendWithdrawalPlan sends the withdrawals of plan in order, to opts.Recipient and with
+// the fee recipient and signer of opts, the other options are the ones of each planned
+// withdrawal. The change notes are owned by the spender. It returns the withdrawals
+// sent, up to the first failure.
+func (f *Frontend) SendWithdrawalPlan(plan *WithdrawalPlan, opts *WithdrawalOpts,
+	spenderPrivkey *eddsa.PrivateKey) ([]*Withdrawal, error) {
+
+	var sent []*Withdrawal
+	for i, planned := range plan.Withdrawals {
+		w, err := f.SendWithdrawal(&WithdrawalOpts{
+			Recipient:    opts.Recipient,
+			FeeRecipient: opts.FeeRecipient,
+			FeeSigner:    opts.FeeSigner,
+			Amount:       planned.Amount,
+			Fee:          planned.Fee,
+			NoChange:     planned.NoChange,
+			FromNote:     planned.FromNote,
+		}, spenderPrivkey, ReceivingKey(*spenderPrivkey))
+		if err != nil {
+			return sent, fmt.Errorf("failed to send withdrawal %d of %d: %v", i+1,
+				len(plan.Withdrawals), err)
+		}
+		sent = append(sent, w)
+	}
+	return sent, nil
+}
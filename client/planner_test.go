@@ -0,0 +1,76 @@
+package client
+
+import (
+	"errors"
+	"math"
+	"testing"
+)
+
+func plannerNotes(amounts ...uint64) []*Note {
+	notes := make([]*Note, len(amounts))
+	for i, amount := range amounts {
+		notes[i] = &Note{Amount: amount, InsertedIndex: i}
+	}
+	return notes
+}
+
+func TestPlanWithdrawalSingleNote(t *testing.T) {
+	fee := DefaultWithdrawalFee(0)
+	notes := plannerNotes(5_000_000, 2_000_000, 3_000_000)
+
+	// the smallest note paying the amount and the fee is used
+	plan, err := PlanWithdrawal(notes, 0, 2_500_000, 0)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(plan.Withdrawals) != 1 || plan.Withdrawals[0].FromNote != notes[2] ||
+		plan.Fees != fee || plan.Withdrawals[0].Change != 3_000_000-2_500_000-fee {
+		t.Fatalf("expected a single withdrawal from the 3 algo note")
+	}
+
+	// no change note when the note is spent exactly
+	plan, err = PlanWithdrawal(notes, 0, 2_000_000-fee, 0)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(plan.Withdrawals) != 1 || !plan.Withdrawals[0].NoChange {
+		t.Fatalf("expected a withdrawal without change")
+	}
+}
+
+func TestPlanWithdrawalSeveralNotes(t *testing.T) {
+	fee := uint64(20_000)
+	notes := plannerNotes(200_000, 100_000, 60_000, 10_000)
+	notes = append(notes, &Note{Amount: 2_000_000, AssetId: 7, InsertedIndex: 4})
+	notes = append(notes, &Note{Amount: 2_000_000, InsertedIndex: -1})
+
+	plan, err := PlanWithdrawal(notes, 0, 240_000, fee)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(plan.Withdrawals) != 2 || plan.Fees != 2*fee {
+		t.Fatalf("expected 2 withdrawals, got %d", len(plan.Withdrawals))
+	}
+	first, last := plan.Withdrawals[0], plan.Withdrawals[1]
+	if first.FromNote != notes[0] || first.Amount != 180_000 || !first.NoChange ||
+		last.FromNote != notes[1] || last.Amount != 60_000 || last.Change != 20_000 {
+		t.Fatalf("unexpected withdrawals %+v %+v", first, last)
+	}
+
+	// the note of 10_000 cannot pay its fee, other assets and notes not in the tree
+	// are ignored: at most 180_000 + 80_000 + 40_000 can be withdrawn
+	_, err = PlanWithdrawal(notes, 0, 300_001, fee)
+	if !errors.Is(err, ErrInsufficientFunds) {
+		t.Fatalf("expected ErrInsufficientFunds, got %v", err)
+	}
+	_, err = PlanWithdrawal(notes, 0, math.MaxUint64, fee)
+	if !errors.Is(err, ErrInsufficientFunds) {
+		t.Fatalf("expected ErrInsufficientFunds for an overflowing amount, got %v", err)
+	}
+
+	// a fee below the nullifier MBR and the transaction fee could not be sent
+	_, err = PlanWithdrawal(notes, 0, 1_000, 100)
+	if err == nil || errors.Is(err, ErrInsufficientFunds) {
+		t.Fatalf("expected a fee error, got %v", err)
+	}
+}
@@ -0,0 +1,283 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"math/big"
+	"net/http"
+	"reflect"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+
+	"github.com/consensys/gnark/frontend"
+	ap "github.com/giuliop/algoplonk"
+	"github.com/giuliop/algoplonk/utils"
+)
+
+// Prover generates the zk-proofs of the app methods. The frontend proves with a
+// LocalProver unless its Prover is set, e.g. to a RemoteProver.
+type Prover interface {
+	// Prove proves assignment of the circuit of the app method
+	Prove(ctx context.Context, method string, assignment frontend.Circuit) (*Proof, error)
+}
+
+// Proof is a marshalled proof and its marshalled public inputs, as taken by
+// utils.ProofAndPublicInputsForAtomicComposer
+type Proof struct {
+	Proof        []byte `json:"proof"`
+	PublicInputs []byte `json:"publicInputs"`
+}
+
+// MethodArgs returns the proof and public inputs arguments of the app method call
+func (p *Proof) MethodArgs() ([]interface{}, error) {
+	args, err := utils.ProofAndPublicInputsForAtomicComposer(p.Proof, p.PublicInputs)
+	if err != nil {
+		return nil, fmt.Errorf("failed to abi encode proof and public inputs: %v", err)
+	}
+	return args, nil
+}
+
+// CompiledCircuit returns the compiled circuit of the app method, or
+// ErrUnsupportedMethod if the app artefacts have none
+func (a *App) CompiledCircuit(method string) (*ap.CompiledCircuit, error) {
+	var cc *ap.CompiledCircuit
+	switch method {
+	case DepositMethod:
+		cc = a.DepositCc
+	case WithDrawalMethod:
+		cc = a.WithdrawalCc
+	case AddressWithdrawalMethod:
+		cc = a.AddressWithdrawalCc
+	case TransferMethod:
+		cc = a.TransferCc
+	case JoinSplitMethod:
+		cc = a.JoinSplitCc
+	default:
+		return nil, fmt.Errorf("no circuit for method %s", method)
+	}
+	if cc == nil {
+		return nil, fmt.Errorf("%w: no compiled %s circuit", ErrUnsupportedMethod, method)
+	}
+	return cc, nil
+}
+
+// NewAssignment returns an empty assignment of the circuit of the app method
+func NewAssignment(method string) (frontend.Circuit, error) {
+	switch method {
+	case DepositMethod:
+		return &circuits.DepositCircuit{}, nil
+	case WithDrawalMethod:
+		return &circuits.WithdrawalCircuit{}, nil
+	case AddressWithdrawalMethod:
+		return &circuits.AddressWithdrawalCircuit{}, nil
+	case TransferMethod:
+		return &circuits.TransferCircuit{}, nil
+	case JoinSplitMethod:
+		return &circuits.JoinSplitCircuit{}, nil
+	}
+	return nil, fmt.Errorf("no circuit for method %s", method)
+}
+
+// LocalProver proves in process with the compiled circuits of App
+type LocalProver struct {
+	App *App
+}
+
+func (p *LocalProver) Prove(_ context.Context, method string, assignment frontend.Circuit) (
+	*Proof, error) {
+
+	cc, err := p.App.CompiledCircuit(method)
+	if err != nil {
+		return nil, err
+	}
+	verifiedProof, err := cc.Verify(assignment)
+	if err != nil {
+		return nil, fmt.Errorf("failed to verify %s proof: %v", method, err)
+	}
+	publicInputs, err := ap.MarshalPublicInputs(verifiedProof.Witness)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal public inputs: %v", err)
+	}
+	return &Proof{
+		Proof:        ap.MarshalProof(verifiedProof.Proof),
+		PublicInputs: publicInputs,
+	}, nil
+}
+
+// ProveRequest is the body of the POST /prove request of a prover daemon: the app
+// method and the assignment of its circuit encoded with EncodeAssignment. The daemon
+// answers with a Proof, or an ErrorResponse.
+type ProveRequest struct {
+	Method     string          `json:"method"`
+	Assignment json.RawMessage `json:"assignment"`
+}
+
+// ErrorResponse is the body of the failed responses of the prover and relayer daemons
+type ErrorResponse struct {
+	Error string `json:"error"`
+}
+
+// RemoteProver proves with the prover daemon at URL (see the prover package).
+// The assignments hold the note secrets and the nullifier key of spenders, the daemon
+// must be trusted with them. It cannot spend: the spender signs every public output
+// of a spend, so the daemon cannot prove other outputs.
+type RemoteProver struct {
+	URL    string
+	Client *http.Client // http.DefaultClient if nil
+}
+
+func (p *RemoteProver) Prove(ctx context.Context, method string, assignment frontend.Circuit) (
+	*Proof, error) {
+
+	encoded, err := EncodeAssignment(assignment)
+	if err != nil {
+		return nil, err
+	}
+	proof := &Proof{}
+	err = postJSON(ctx, p.Client, p.URL+"/prove",
+		&ProveRequest{Method: method, Assignment: encoded}, proof)
+	if err != nil {
+		return nil, fmt.Errorf("prover failed to prove %s: %v", method, err)
+	}
+	return proof, nil
+}
+
+// postJSON posts request as JSON to url and decodes the JSON response into response,
+// or returns the error of the ErrorResponse answered
+func postJSON(ctx context.Context, client *http.Client, url string, request,
+	response interface{}) error {
+
+	body, err := json.Marshal(request)
+	if err != nil {
+		return fmt.Errorf("failed to encode request: %v", err)
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(body))
+	if err != nil {
+		return fmt.Errorf("failed to create request: %v", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	if client == nil {
+		client = http.DefaultClient
+	}
+	resp, err := client.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to send request: %v", err)
+	}
+	defer resp.Body.Close()
+	respBody, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return fmt.Errorf("failed to read response: %v", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		errResp := ErrorResponse{}
+		if json.Unmarshal(respBody, &errResp) != nil || errResp.Error == "" {
+			errResp.Error = resp.Status
+		}
+		return errors.New(errResp.Error)
+	}
+	if err := json.Unmarshal(respBody, response); err != nil {
+		return fmt.Errorf("failed to decode response: %v", err)
+	}
+	return nil
+}
+
+// prove proves assignment of the circuit of method with the frontend Prover and
+// returns the method arguments of the proof and public inputs
+func (f *Frontend) prove(method string, assignment frontend.Circuit) ([]interface{}, error) {
+	proof, err := f.proof(method, assignment)
+	if err != nil {
+		return nil, err
+	}
+	return proof.MethodArgs()
+}
+
+// proof proves assignment of the circuit of method with the frontend Prover
+func (f *Frontend) proof(method string, assignment frontend.Circuit) (*Proof, error) {
+	prover := f.Prover
+	if prover == nil {
+		prover = &LocalProver{App: f.App}
+	}
+	return prover.Prove(context.Background(), method, assignment)
+}
+
+// EncodeAssignment returns the JSON of assignment with the struct fields of the circuit
+// and every variable as a decimal string, which decodes back into the circuit struct
+// (see NewAssignment) as an assignment of the same values
+func EncodeAssignment(assignment frontend.Circuit) (json.RawMessage, error) {
+	encoded, err := encodeVariables(reflect.ValueOf(assignment), "assignment")
+	if err != nil {
+		return nil, err
+	}
+	return json.Marshal(encoded)
+}
+
+// encodeVariables replaces the variables of v with decimal strings, keeping the
+// structs and arrays holding them
+func encodeVariables(v reflect.Value, name string) (interface{}, error) {
+	switch v.Kind() {
+	case reflect.Pointer:
+		if v.IsNil() {
+			return nil, fmt.Errorf("unassigned %s", name)
+		}
+		return encodeVariables(v.Elem(), name)
+	case reflect.Struct:
+		fields := map[string]interface{}{}
+		for i := 0; i < v.NumField(); i++ {
+			field := v.Type().Field(i)
+			if !field.IsExported() {
+				continue
+			}
+			encoded, err := encodeVariables(v.Field(i), name+"."+field.Name)
+			if err != nil {
+				return nil, err
+			}
+			fields[field.Name] = encoded
+		}
+		return fields, nil
+	case reflect.Array:
+		values := make([]interface{}, v.Len())
+		for i := range values {
+			encoded, err := encodeVariables(v.Index(i), fmt.Sprintf("%s[%d]", name, i))
+			if err != nil {
+				return nil, err
+			}
+			values[i] = encoded
+		}
+		return values, nil
+	case reflect.Interface:
+		if v.IsNil() {
+			return nil, fmt.Errorf("unassigned %s", name)
+		}
+		return variableString(v.Elem().Interface(), name)
+	}
+	return nil, fmt.Errorf("unsupported %s of kind %s", name, v.Kind())
+}
+
+// variableString returns the decimal string of the value of a variable
+func variableString(value interface{}, name string) (string, error) {
+	n := new(big.Int)
+	switch x := value.(type) {
+	case []byte:
+		n.SetBytes(x)
+	case uint64:
+		n.SetUint64(x)
+	case int:
+		n.SetInt64(int64(x))
+	case *big.Int:
+		n.Set(x)
+	case big.Int:
+		n.Set(&x)
+	case string:
+		if _, ok := n.SetString(x, 0); !ok {
+			return "", fmt.Errorf("invalid %s: %q", name, x)
+		}
+	default:
+		return "", fmt.Errorf("unsupported %s of type %T", name, value)
+	}
+	return n.String(), nil
+}
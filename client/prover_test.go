@@ -0,0 +1,58 @@
+package client
+
+import (
+	"encoding/json"
+	"math/big"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+)
+
+func TestEncodeAssignment(t *testing.T) {
+	assignment := &circuits.DepositCircuit{
+		Amount:     uint64(1_000_000),
+		AssetId:    0,
+		Commitment: []byte{1, 2},
+		K:          big.NewInt(7),
+		R:          "0x10",
+		OutputX:    []byte{3},
+		OutputY:    []byte{4},
+	}
+	encoded, err := EncodeAssignment(assignment)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	decoded, err := NewAssignment(DepositMethod)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := json.Unmarshal(encoded, decoded); err != nil {
+		t.Fatalf("failed to decode assignment: %v", err)
+	}
+	deposit := decoded.(*circuits.DepositCircuit)
+	fields := map[string]interface{}{
+		"Amount":     deposit.Amount,
+		"AssetId":    deposit.AssetId,
+		"Commitment": deposit.Commitment,
+		"K":          deposit.K,
+		"R":          deposit.R,
+	}
+	expected := map[string]string{
+		"Amount":     "1000000",
+		"AssetId":    "0",
+		"Commitment": "258",
+		"K":          "7",
+		"R":          "16",
+	}
+	for name, value := range expected {
+		if fields[name] != value {
+			t.Fatalf("%s decoded as %v, expected %s", name, fields[name], value)
+		}
+	}
+
+	// every variable must be assigned
+	if _, err := EncodeAssignment(&circuits.DepositCircuit{Amount: 1}); err == nil {
+		t.Fatalf("expected an unassigned variable to fail")
+	}
+}
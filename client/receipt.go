@@ -0,0 +1,228 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"crypto/ed25519"
+	"encoding/binary"
+	"fmt"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// DepositReceiptVersion is the version of the deposit receipts created by
+// NewDepositReceipt
+const DepositReceiptVersion = 1
+
+// depositReceiptDomain prefixes the bytes signed by the depositor of a receipt
+const depositReceiptDomain = "mithras-deposit-receipt"
+
+// DepositReceipt is the proof by a depositor, e.g. an NGO, that it made a deposit to
+// the app: the deposit transaction and the note it inserted in the tree, signed by
+// the depositor account. It optionally discloses the recipient of the note, so that
+// donors and regulators can audit a disbursement programme with VerifyDepositReceipt.
+type DepositReceipt struct {
+	Version    byte
+	AppId      uint64
+	Round      uint64 // the round the deposit was confirmed in
+	TxnId      string // of the deposit app call
+	Depositor  string // the address paying the deposit, the `sender` of the deposit
+	Amount     uint64
+	AssetId    uint64
+	Commitment []byte
+	LeafIndex  uint64
+	Disclosure *RecipientDisclosure `json:",omitempty"`
+	// Signature is the signature by the Depositor account of SignedBytes
+	Signature []byte
+}
+
+// RecipientDisclosure opens the commitment of a deposit receipt to the note recipient:
+// its public key coordinates, or OwnerAddress for notes owned by an address. It does
+// not reveal whether the note was spent, which takes the nullifier key of the
+// recipient (see AuditHistory).
+type RecipientDisclosure struct {
+	OutputX      []byte
+	OutputY      []byte
+	OwnerAddress string `json:",omitempty"`
+	K            []byte
+	R            []byte
+}
+
+// NewDepositReceipt returns the receipt of deposit d signed by depositor, which made
+// it, disclosing the recipient of the note if disclose is set
+func (f *Frontend) NewDepositReceipt(d *Deposit, depositor *crypto.Account, disclose bool) (
+	*DepositReceipt, error) {
+
+	if d.FromAddress != depositor.Address.String() {
+		return nil, fmt.Errorf("deposit made by %s, not %s", d.FromAddress, depositor.Address)
+	}
+	note := d.Note
+	if len(d.TxnIds) == 0 || note.InsertedIndex < 0 {
+		return nil, fmt.Errorf("deposit not confirmed")
+	}
+	r := &DepositReceipt{
+		Version:    DepositReceiptVersion,
+		AppId:      f.App.Id,
+		Round:      d.Round,
+		TxnId:      d.TxnIds[0],
+		Depositor:  d.FromAddress,
+		Amount:     note.Amount,
+		AssetId:    note.AssetId,
+		Commitment: note.Commitment,
+		LeafIndex:  uint64(note.InsertedIndex),
+	}
+	if disclose {
+		r.Disclosure = &RecipientDisclosure{
+			OutputX: note.OutputX,
+			OutputY: note.OutputY,
+			K:       note.K,
+			R:       note.R,
+		}
+		if !note.OwnerAddress.IsZero() {
+			r.Disclosure.OwnerAddress = note.OwnerAddress.String()
+		}
+	}
+	signature, err := crypto.SignBytes(depositor.PrivateKey, r.SignedBytes())
+	if err != nil {
+		return nil, fmt.Errorf("failed to sign receipt: %v", err)
+	}
+	r.Signature = signature
+	return r, nil
+}
+
+// SignedBytes returns the bytes of the receipt signed by the depositor: the receipt
+// fields but the signature in order, after depositReceiptDomain, with the integers
+// as 8 bytes big endian and the byte and string fields prefixed by their length.
+// The disclosure fields follow a byte set to 1 if the receipt has one, 0 otherwise.
+func (r *DepositReceipt) SignedBytes() []byte {
+	b := []byte(depositReceiptDomain)
+	b = append(b, r.Version)
+	b = binary.BigEndian.AppendUint64(b, r.AppId)
+	b = binary.BigEndian.AppendUint64(b, r.Round)
+	b = appendBytes(b, []byte(r.TxnId))
+	b = appendBytes(b, []byte(r.Depositor))
+	b = binary.BigEndian.AppendUint64(b, r.Amount)
+	b = binary.BigEndian.AppendUint64(b, r.AssetId)
+	b = appendBytes(b, r.Commitment)
+	b = binary.BigEndian.AppendUint64(b, r.LeafIndex)
+	if r.Disclosure == nil {
+		return append(b, 0)
+	}
+	b = append(b, 1)
+	for _, field := range [][]byte{r.Disclosure.OutputX, r.Disclosure.OutputY,
+		[]byte(r.Disclosure.OwnerAddress), r.Disclosure.K, r.Disclosure.R} {
+		b = appendBytes(b, field)
+	}
+	return b
+}
+
+// appendBytes appends to b the length of field as 2 bytes big endian and field
+func appendBytes(b, field []byte) []byte {
+	b = binary.BigEndian.AppendUint16(b, uint16(len(field)))
+	return append(b, field...)
+}
+
+// VerifyDepositReceipt checks receipt against the chain history read from source:
+// its signature by the depositor, that its transaction is a deposit to the app
+// confirmed in its round, paid by the depositor, whose DepositCircuit public inputs
+// are its amount, asset and commitment and which inserted the commitment at its leaf
+// index, and that its disclosure, if any, opens the commitment. Source reads the
+// calls from the round of the receipt, an IndexerSource is faster for old receipts.
+func (f *Frontend) VerifyDepositReceipt(ctx context.Context, source TxnSource,
+	receipt *DepositReceipt) error {
+
+	if err := receipt.checkSignature(); err != nil {
+		return err
+	}
+	if receipt.AppId != f.App.Id {
+		return fmt.Errorf("receipt of app %d, not %d", receipt.AppId, f.App.Id)
+	}
+	if err := f.checkDisclosure(receipt); err != nil {
+		return err
+	}
+
+	calls, _, err := source.AppCalls(ctx, f.App.Id, receipt.Round)
+	if err != nil {
+		return fmt.Errorf("failed to read app calls: %v", err)
+	}
+	var call *AppCall
+	for i := range calls {
+		if calls[i].TxID == receipt.TxnId && calls[i].Round == receipt.Round {
+			call = &calls[i]
+			break
+		}
+	}
+	if call == nil {
+		return fmt.Errorf("transaction %s not found in round %d", receipt.TxnId,
+			receipt.Round)
+	}
+
+	scanner, err := f.NewScanner(source)
+	if err != nil {
+		return err
+	}
+	event, err := scanner.parseEvent(*call)
+	if err != nil {
+		return fmt.Errorf("failed to parse transaction %s: %v", call.TxID, err)
+	}
+	if event == nil || event.Method != DepositMethod {
+		return fmt.Errorf("transaction %s is not a deposit", call.TxID)
+	}
+	// args: selector, proof, public inputs, sender
+	// public inputs: amount, asset_id, commitment
+	if len(call.Args) < 4 || len(call.Args[3]) != len(types.Address{}) {
+		return fmt.Errorf("missing deposit sender")
+	}
+	var sender types.Address
+	copy(sender[:], call.Args[3])
+	switch {
+	case sender.String() != receipt.Depositor:
+		return fmt.Errorf("deposit paid by %s, not %s", sender, receipt.Depositor)
+	case binary.BigEndian.Uint64(event.PublicInputs[0][24:]) != receipt.Amount:
+		return fmt.Errorf("deposit amount does not match")
+	case event.AssetId != receipt.AssetId:
+		return fmt.Errorf("deposit asset does not match")
+	case !bytes.Equal(event.Commitments[0], receipt.Commitment):
+		return fmt.Errorf("deposit commitment does not match")
+	case event.LeafIndex != receipt.LeafIndex:
+		return fmt.Errorf("deposit leaf index %d, not %d", event.LeafIndex, receipt.LeafIndex)
+	}
+	return nil
+}
+
+// checkSignature checks the receipt is signed by its depositor
+func (r *DepositReceipt) checkSignature() error {
+	depositor, err := types.DecodeAddress(r.Depositor)
+	if err != nil {
+		return fmt.Errorf("invalid depositor: %v", err)
+	}
+	if !crypto.VerifyBytes(ed25519.PublicKey(depositor[:]), r.SignedBytes(), r.Signature) {
+		return fmt.Errorf("invalid receipt signature")
+	}
+	return nil
+}
+
+// checkDisclosure checks the disclosure of receipt, if any, opens its commitment
+func (f *Frontend) checkDisclosure(receipt *DepositReceipt) error {
+	d := receipt.Disclosure
+	if d == nil {
+		return nil
+	}
+	if d.OwnerAddress != "" {
+		owner, err := types.DecodeAddress(d.OwnerAddress)
+		if err != nil {
+			return fmt.Errorf("invalid disclosed owner: %v", err)
+		}
+		x, y := AddressOwnerCoordinates(owner)
+		if !bytes.Equal(x, d.OutputX) || !bytes.Equal(y, d.OutputY) {
+			return fmt.Errorf("disclosed owner does not match its coordinates")
+		}
+	}
+	commitment := f.makeCommitment(receipt.Amount, receipt.AssetId, d.K, d.R, d.OutputX,
+		d.OutputY)
+	if !bytes.Equal(commitment, receipt.Commitment) {
+		return fmt.Errorf("disclosure does not open the receipt commitment")
+	}
+	return nil
+}
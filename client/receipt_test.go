@@ -0,0 +1,66 @@
+package client
+
+import (
+	"crypto/rand"
+	"testing"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+func TestDepositReceiptSignatureAndDisclosure(t *testing.T) {
+	f := newTestFrontend()
+	f.App = &App{Id: 7}
+	depositor := crypto.GenerateAccount()
+	key, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	note, _, err := f.NewNote(5_000_000, *key, ReceivingKey(*key))
+	if err != nil {
+		t.Fatal(err)
+	}
+	note.InsertedIndex = 3
+	deposit := &Deposit{
+		FromAddress: depositor.Address.String(),
+		TxnIds:      []string{"TXID"},
+		Round:       12,
+		Note:        note,
+	}
+
+	if _, err := f.NewDepositReceipt(deposit, &crypto.Account{}, true); err == nil {
+		t.Fatalf("expected a receipt signed by another account to fail")
+	}
+	receipt, err := f.NewDepositReceipt(deposit, &depositor, true)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := receipt.checkSignature(); err != nil {
+		t.Fatalf("receipt signature does not verify: %v", err)
+	}
+	if err := f.checkDisclosure(receipt); err != nil {
+		t.Fatalf("receipt disclosure does not verify: %v", err)
+	}
+
+	// the signature covers every field
+	receipt.LeafIndex++
+	if receipt.checkSignature() == nil {
+		t.Fatalf("expected a tampered receipt to fail")
+	}
+	receipt.LeafIndex--
+
+	// the disclosure must open the commitment
+	receipt.Disclosure.R = note.K
+	if f.checkDisclosure(receipt) == nil {
+		t.Fatalf("expected a wrong disclosure to fail")
+	}
+
+	// receipts without disclosure verify as well
+	receipt, err = f.NewDepositReceipt(deposit, &depositor, false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if receipt.Disclosure != nil || receipt.checkSignature() != nil {
+		t.Fatalf("expected a valid receipt without disclosure")
+	}
+}
@@ -0,0 +1,191 @@
+package client
+
+import (
+	"context"
+	"fmt"
+	"math/big"
+	"net/http"
+
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// withdrawalInputsCount is the number of public inputs of the withdrawal circuit
+const withdrawalInputsCount = 8
+
+// RelayedWithdrawal is a proved withdrawal of an Algo note that a relayer sends on
+// behalf of a user without Algo to pay the transaction fees, see PrepareWithdrawal.
+// It is the body of the POST /relay request of a relayer daemon, which answers with
+// a RelayResult, or an ErrorResponse.
+// The fee recipient is not part of the proof: the relayer chooses it, and the fee of
+// the proof must pay for it.
+type RelayedWithdrawal struct {
+	Proof     *Proof `json:"proof"`
+	Recipient string `json:"recipient"`
+	NoChange  bool   `json:"noChange"`
+	// Note is the note field of the app call, with the encrypted output notes
+	Note []byte `json:"note"`
+}
+
+// RelayResult is the result of a relayed withdrawal
+type RelayResult struct {
+	TxnIds    []string `json:"txnIds"`
+	LeafIndex uint64   `json:"leafIndex"` // of the change note
+	Root      []byte   `json:"root"`
+}
+
+// WithdrawalInputs are the public inputs of a withdrawal proof
+type WithdrawalInputs struct {
+	RecipientMod      []byte
+	Amount            uint64
+	Fee               uint64
+	AssetId           uint64
+	Nullifier         []byte
+	Root              []byte
+	UnspentCommitment []byte
+	SpentCommitment   []byte
+}
+
+// Inputs parses the public inputs of the withdrawal proof
+func (w *RelayedWithdrawal) Inputs() (*WithdrawalInputs, error) {
+	if w.Proof == nil || len(w.Proof.PublicInputs) != 32*withdrawalInputsCount {
+		return nil, fmt.Errorf("expected %d public inputs", withdrawalInputsCount)
+	}
+	input := func(i int) []byte {
+		return w.Proof.PublicInputs[32*i : 32*(i+1)]
+	}
+	var uints [3]uint64
+	for i := range uints {
+		n := new(big.Int).SetBytes(input(i + 1))
+		if !n.IsUint64() {
+			return nil, fmt.Errorf("public input %d is not a uint64", i+1)
+		}
+		uints[i] = n.Uint64()
+	}
+	return &WithdrawalInputs{
+		RecipientMod:      input(0),
+		Amount:            uints[0],
+		Fee:               uints[1],
+		AssetId:           uints[2],
+		Nullifier:         input(4),
+		Root:              input(5),
+		UnspentCommitment: input(6),
+		SpentCommitment:   input(7),
+	}, nil
+}
+
+// PrepareWithdrawal proves the withdrawal of opts of an Algo note for a relayer to
+// send with SendRelayedWithdrawal. opts.Fee must pay the fee of the relayer, which
+// chooses the fee recipient, so opts.FeeRecipient and opts.FeeSigner are not used.
+// It returns the request to the relayer and the withdrawal to add to the frontend
+// with AddRelayedWithdrawal once relayed.
+func (f *Frontend) PrepareWithdrawal(opts *WithdrawalOpts, spenderPrivkey *eddsa.PrivateKey,
+	outputPubkey eddsa.PublicKey) (*RelayedWithdrawal, *Withdrawal, error) {
+
+	if opts.FromNote.AssetId != 0 {
+		return nil, nil, fmt.Errorf("only Algo withdrawals can be relayed")
+	}
+	g, err := f.newSpendGroup(opts.FromNote, 1, opts.Fee, types.ZeroAddress, nil)
+	if err != nil {
+		return nil, nil, err
+	}
+	unspentNote, spendNote, err := f.assignWithdrawal(g, opts, spenderPrivkey, outputPubkey)
+	if err != nil {
+		return nil, nil, err
+	}
+	proof, err := f.proof(g.method, g.assignment)
+	if err != nil {
+		return nil, nil, err
+	}
+	req := &RelayedWithdrawal{
+		Proof:     proof,
+		Recipient: opts.Recipient.String(),
+		NoChange:  opts.NoChange,
+		Note:      g.note,
+	}
+	w := &Withdrawal{
+		ToAddress: opts.Recipient.String(),
+		Note:      unspentNote,
+		SentNote:  spendNote,
+	}
+	return req, w, nil
+}
+
+// AddRelayedWithdrawal adds to the frontend the withdrawal w of PrepareWithdrawal,
+// relayed with the request req and result res: its output notes are added to the tree
+// unless NoChange is set, and the frontend NoteDerivation advances past them
+func (f *Frontend) AddRelayedWithdrawal(req *RelayedWithdrawal, w *Withdrawal,
+	res *RelayResult) {
+
+	w.TxnIds = res.TxnIds
+	f.confirmNotes()
+	f.addWithdrawal(w, req.NoChange, res.LeafIndex)
+}
+
+// SendRelayedWithdrawal sends the proved withdrawal w paying its fee to feeRecipient,
+// which pays the transaction fees of the group with the padding transactions signed
+// by feeSigner, after simulating the group. The fee of w must be at least the
+// transaction fees and the nullifier MBR (see DefaultWithdrawalFee); the caller checks
+// it is also worth relaying.
+func (f *Frontend) SendRelayedWithdrawal(w *RelayedWithdrawal, feeRecipient types.Address,
+	feeSigner transaction.TransactionSigner) (*RelayResult, error) {
+
+	inputs, err := w.Inputs()
+	if err != nil {
+		return nil, err
+	}
+	if inputs.AssetId != 0 {
+		return nil, fmt.Errorf("only Algo withdrawals can be relayed")
+	}
+	if inputs.Fee < DefaultWithdrawalFee(0) {
+		return nil, fmt.Errorf("fee %d cannot pay the transaction fees and nullifier MBR %d",
+			inputs.Fee, DefaultWithdrawalFee(0))
+	}
+	recipient, err := types.DecodeAddress(w.Recipient)
+	if err != nil {
+		return nil, fmt.Errorf("invalid recipient: %v", err)
+	}
+	proofArgs, err := w.Proof.MethodArgs()
+	if err != nil {
+		return nil, err
+	}
+
+	g := &spendGroup{
+		method:       WithDrawalMethod,
+		verifier:     f.App.WithdrawalVerifier,
+		nullifiers:   [][]byte{inputs.Nullifier},
+		fee:          inputs.Fee,
+		feeRecipient: feeRecipient,
+		feeSigner:    feeSigner,
+		note:         w.Note,
+	}
+	g.setWithdrawalArgs(recipient, w.NoChange)
+	res, leafIndex, err := f.sendProvedSpendGroup(g, proofArgs)
+	if err != nil {
+		return nil, err
+	}
+	_, root, err := parseResult(res)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method result: %v", err)
+	}
+	return &RelayResult{TxnIds: res.TxIDs, LeafIndex: leafIndex, Root: root}, nil
+}
+
+// RemoteRelayer sends withdrawals with the relayer daemon at URL (see the relayer
+// package)
+type RemoteRelayer struct {
+	URL    string
+	Client *http.Client // http.DefaultClient if nil
+}
+
+// Relay asks the relayer to send the withdrawal req
+func (r *RemoteRelayer) Relay(ctx context.Context, req *RelayedWithdrawal) (*RelayResult,
+	error) {
+
+	res := &RelayResult{}
+	if err := postJSON(ctx, r.Client, r.URL+"/relay", req, res); err != nil {
+		return nil, fmt.Errorf("relayer failed to relay withdrawal: %v", err)
+	}
+	return res, nil
+}
@@ -0,0 +1,271 @@
+package client
+
+import (
+	"context"
+	"encoding/binary"
+	"fmt"
+	"math"
+	"math/bits"
+	"sort"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/common/models"
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+// Reserves is the accounting of the app escrow checked by CheckReserves: for each
+// asset, the value of the unspent notes computed from the public amounts of the app
+// history against the app account balance, which for Algo also holds the MBR of the
+// app, of its nullifier and depositor boxes and of its asset opt-ins
+type Reserves struct {
+	Round uint64 // the round of the app account balances
+
+	// Assets are the reserves of each asset, 0 for Algo, in asset id order
+	Assets []*AssetReserves
+
+	// Nullifiers is the number of notes spent in the app history, NullifierBoxes the
+	// number of nullifier boxes of the app
+	Nullifiers     int
+	NullifierBoxes int
+	// Depositors is the number of depositors in the deposit allowlist
+	Depositors    int
+	OptedInAssets int
+
+	// MinBalance is the minimum balance of the app account, expected to be
+	// config.InitialMbr plus the MBR of its nullifier and depositor boxes and asset
+	// opt-ins
+	MinBalance         uint64
+	ExpectedMinBalance uint64
+
+	// Discrepancies describes each check that failed, none if the reserves match
+	Discrepancies []string
+}
+
+// AssetReserves are the reserves of an asset, 0 for Algo
+type AssetReserves struct {
+	AssetId uint64
+
+	// Deposited, Withdrawn and Fees are the sums of the public amounts of the
+	// deposits, of the withdrawals and of the fees of the spending methods
+	Deposited uint64
+	Withdrawn uint64
+	Fees      uint64
+	// Unspent is the value of the unspent notes, Deposited - Withdrawn - Fees. It
+	// includes the change lost by withdrawals with `no_change`, which no note can spend.
+	Unspent int64
+
+	// Overflow is set when the sums or the values computed from them do not fit in
+	// their fields, which are then not computed
+	Overflow bool
+
+	// Balance is the balance of the app account, Expected the balance expected from
+	// Unspent, plus the app MBR for Algo. Surplus is Balance - Expected: positive
+	// when the app received payments outside its methods, negative when notes are
+	// not backed by the escrow.
+	Balance  uint64
+	Expected int64
+	Surplus  int64
+}
+
+// reservesAttempts is the number of times CheckReserves reads the app history and
+// account before giving up if the account keeps changing
+const reservesAttempts = 5
+
+// CheckReserves checks that the app escrow backs the unspent notes: it sums the
+// public amounts of the deposits and spends of the app history read from source,
+// reads the balances and the nullifier boxes of the app account, and reports any
+// discrepancy in the returned Reserves. Source must cover the history from the app
+// creation block, as a Scanner. The reads are retried until the app account is the
+// same before and after them, so that they are a snapshot of a single round.
+func (f *Frontend) CheckReserves(ctx context.Context, source TxnSource) (*Reserves, error) {
+	scanner, err := f.NewScanner(source)
+	if err != nil {
+		return nil, err
+	}
+	appAddress := crypto.GetApplicationAddress(f.App.Id).String()
+	for attempt := 0; attempt < reservesAttempts; attempt++ {
+		account, err := f.algod.AccountInformation(appAddress).Do(ctx)
+		if err != nil {
+			return nil, fmt.Errorf("failed to get app account: %v", err)
+		}
+		calls, lastRound, err := source.AppCalls(ctx, f.App.Id, f.App.CreationBlock)
+		if err != nil {
+			return nil, fmt.Errorf("failed to read app calls: %v", err)
+		}
+		boxes, err := f.algod.GetApplicationBoxes(f.App.Id).Do(ctx)
+		if err != nil {
+			return nil, fmt.Errorf("failed to get app boxes: %v", err)
+		}
+		after, err := f.algod.AccountInformation(appAddress).Do(ctx)
+		if err != nil {
+			return nil, fmt.Errorf("failed to get app account: %v", err)
+		}
+		// the reads are consistent if no round passed meanwhile, and the source is
+		// complete if it reached that round
+		if !sameAccountState(account, after) || lastRound < after.Round {
+			continue
+		}
+
+		r := newReserves()
+		for _, call := range calls {
+			if call.Round > after.Round {
+				continue
+			}
+			event, err := scanner.parseEvent(call)
+			if err != nil {
+				return nil, fmt.Errorf("failed to parse transaction %s: %v", call.TxID, err)
+			}
+			if event != nil {
+				r.addEvent(event)
+			}
+		}
+		for _, box := range boxes.Boxes {
+			switch {
+			case len(box.Name) == 32:
+				r.NullifierBoxes++
+			case len(box.Name) == 33 && string(box.Name[:1]) == depositorBoxPrefix:
+				r.Depositors++
+			}
+		}
+		r.check(after)
+		return r, nil
+	}
+	return nil, fmt.Errorf("app account changed during the %d attempts of the check, "+
+		"try again", reservesAttempts)
+}
+
+// sameAccountState returns true if a and b are the app account at the same round,
+// with the same balances
+func sameAccountState(a, b models.Account) bool {
+	if a.Round != b.Round || a.Amount != b.Amount || a.MinBalance != b.MinBalance ||
+		len(a.Assets) != len(b.Assets) {
+		return false
+	}
+	for i := range a.Assets {
+		if a.Assets[i].AssetId != b.Assets[i].AssetId ||
+			a.Assets[i].Amount != b.Assets[i].Amount {
+			return false
+		}
+	}
+	return true
+}
+
+// newReserves returns empty reserves, with the Algo ones
+func newReserves() *Reserves {
+	return &Reserves{Assets: []*AssetReserves{{AssetId: 0}}}
+}
+
+// asset returns the reserves of assetId, adding them if missing
+func (r *Reserves) asset(assetId uint64) *AssetReserves {
+	i := sort.Search(len(r.Assets), func(i int) bool {
+		return r.Assets[i].AssetId >= assetId
+	})
+	if i < len(r.Assets) && r.Assets[i].AssetId == assetId {
+		return r.Assets[i]
+	}
+	a := &AssetReserves{AssetId: assetId}
+	r.Assets = append(r.Assets[:i], append([]*AssetReserves{a}, r.Assets[i:]...)...)
+	return a
+}
+
+// addEvent adds the public amounts and the nullifiers of event to the reserves
+func (r *Reserves) addEvent(event *TreeEvent) {
+	a := r.asset(event.AssetId)
+	// add adds the public input i to sum
+	add := func(sum *uint64, i int) {
+		var carry uint64
+		*sum, carry = bits.Add64(*sum, binary.BigEndian.Uint64(event.PublicInputs[i][24:]), 0)
+		a.Overflow = a.Overflow || carry != 0
+	}
+	switch event.Method {
+	case DepositMethod:
+		// public inputs: amount, asset_id, commitment
+		add(&a.Deposited, 0)
+	case WithDrawalMethod, AddressWithdrawalMethod:
+		// public inputs: recipient_mod, withdrawal, fee, ...
+		add(&a.Withdrawn, 1)
+		add(&a.Fees, 2)
+		r.Nullifiers++
+	case TransferMethod:
+		// public inputs: fee, ...
+		add(&a.Fees, 0)
+		r.Nullifiers++
+	case JoinSplitMethod:
+		// public inputs: fee, ...
+		add(&a.Fees, 0)
+		r.Nullifiers += circuits.JoinSplitInputs
+	}
+}
+
+// difference returns x - y, false if it does not fit in an int64
+func difference(x, y uint64) (int64, bool) {
+	if x >= y {
+		return int64(x - y), x-y <= math.MaxInt64
+	}
+	return -int64(y - x), y-x <= math.MaxInt64
+}
+
+// check compares the reserves of the history to the app account, whose boxes are
+// counted in NullifierBoxes and Depositors, and records the discrepancies
+func (r *Reserves) check(account models.Account) {
+	r.Round = account.Round
+	r.MinBalance = account.MinBalance
+
+	balances := map[uint64]uint64{0: account.Amount}
+	for _, holding := range account.Assets {
+		balances[holding.AssetId] = holding.Amount
+		r.asset(holding.AssetId)
+	}
+	r.OptedInAssets = len(account.Assets)
+	r.ExpectedMinBalance = uint64(config.InitialMbr + r.NullifierBoxes*config.NullifierMbr +
+		r.Depositors*config.DepositorMbr + r.OptedInAssets*config.AssetOptInMbr)
+
+	if r.Nullifiers != r.NullifierBoxes {
+		r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+			"%d nullifiers spent, but %d nullifier boxes", r.Nullifiers, r.NullifierBoxes))
+	}
+	if r.MinBalance != r.ExpectedMinBalance {
+		r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+			"min balance %d, expected %d", r.MinBalance, r.ExpectedMinBalance))
+	}
+	for _, a := range r.Assets {
+		a.Balance = balances[a.AssetId]
+		// Unspent = Deposited - out, Expected = in - out and Surplus = Balance - Expected,
+		// with in the deposits and the MBR for Algo, and out the withdrawals and fees
+		in, inCarry := a.Deposited, uint64(0)
+		if a.AssetId == 0 {
+			in, inCarry = bits.Add64(in, r.ExpectedMinBalance, 0)
+		}
+		out, outCarry := bits.Add64(a.Withdrawn, a.Fees, 0)
+		balanceOut, balanceCarry := bits.Add64(a.Balance, out, 0)
+		var unspentOk, expectedOk, surplusOk bool
+		a.Unspent, unspentOk = difference(a.Deposited, out)
+		a.Expected, expectedOk = difference(in, out)
+		a.Surplus, surplusOk = difference(balanceOut, in)
+		if a.Overflow || inCarry|outCarry|balanceCarry != 0 ||
+			!unspentOk || !expectedOk || !surplusOk {
+			a.Overflow = true
+			a.Unspent, a.Expected, a.Surplus = 0, 0, 0
+			r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+				"asset %d: amounts overflow, %d deposited, %d withdrawn, %d fees",
+				a.AssetId, a.Deposited, a.Withdrawn, a.Fees))
+			continue
+		}
+		if a.Unspent < 0 {
+			r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+				"asset %d: %d withdrawn with fees, more than the %d deposited",
+				a.AssetId, out, a.Deposited))
+		}
+		if a.Surplus < 0 {
+			r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+				"asset %d: balance %d, %d short of the expected %d",
+				a.AssetId, a.Balance, -a.Surplus, a.Expected))
+		} else if a.Surplus > 0 {
+			r.Discrepancies = append(r.Discrepancies, fmt.Sprintf(
+				"asset %d: balance %d, %d over the expected %d",
+				a.AssetId, a.Balance, a.Surplus, a.Expected))
+		}
+	}
+}
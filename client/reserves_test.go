@@ -0,0 +1,85 @@
+package client
+
+import (
+	"math"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/common/models"
+)
+
+// testEvent returns an event of method for assetId with public inputs values
+func testEvent(method string, assetId uint64, values ...uint64) *TreeEvent {
+	inputs := make([][]byte, len(values))
+	for i, v := range values {
+		inputs[i] = uint64ToBytes32(v)
+	}
+	return &TreeEvent{Method: method, AssetId: assetId, PublicInputs: inputs}
+}
+
+func TestReserves(t *testing.T) {
+	const asset = 1234
+	events := []*TreeEvent{
+		// amount, asset_id
+		testEvent(DepositMethod, 0, 100e6, 0),
+		testEvent(DepositMethod, asset, 500, asset),
+		// recipient_mod, withdrawal, fee
+		testEvent(WithDrawalMethod, 0, 0, 10e6, 200_000),
+		testEvent(AddressWithdrawalMethod, asset, 0, 100, 5),
+		// fee
+		testEvent(TransferMethod, 0, 100_000),
+		testEvent(JoinSplitMethod, asset, 10),
+	}
+	r := newReserves()
+	for _, e := range events {
+		r.addEvent(e)
+	}
+	if r.Nullifiers != 5 {
+		t.Fatalf("expected 5 nullifiers, got %d", r.Nullifiers)
+	}
+
+	minBalance := uint64(config.InitialMbr + 5*config.NullifierMbr + config.AssetOptInMbr)
+	algoUnspent := uint64(100e6 - 10e6 - 200_000 - 100_000)
+	account := models.Account{
+		Round:      42,
+		Amount:     minBalance + algoUnspent,
+		MinBalance: minBalance,
+		Assets:     []models.AssetHolding{{AssetId: asset, Amount: 500 - 100 - 5 - 10}},
+	}
+	r.NullifierBoxes = 5
+	r.check(account)
+	if len(r.Discrepancies) != 0 {
+		t.Fatalf("unexpected discrepancies %v", r.Discrepancies)
+	}
+	if len(r.Assets) != 2 || r.Assets[0].AssetId != 0 || r.Assets[1].AssetId != asset {
+		t.Fatalf("unexpected assets %+v", r.Assets)
+	}
+	if r.Assets[0].Unspent != int64(algoUnspent) || r.Assets[1].Unspent != 385 {
+		t.Fatalf("unexpected unspent values %d, %d", r.Assets[0].Unspent,
+			r.Assets[1].Unspent)
+	}
+
+	// a payment to the app outside its methods and a missing nullifier box
+	r = newReserves()
+	for _, e := range events {
+		r.addEvent(e)
+	}
+	account.Amount += 1e6
+	r.NullifierBoxes = 4
+	r.check(account)
+	if r.Assets[0].Surplus != 1e6+config.NullifierMbr || len(r.Discrepancies) != 3 {
+		t.Fatalf("unexpected reserves %+v, discrepancies %v", r.Assets[0],
+			r.Discrepancies)
+	}
+
+	// sums overflowing a uint64 are reported instead of wrapping
+	r = newReserves()
+	r.addEvent(testEvent(DepositMethod, asset, math.MaxUint64, asset))
+	r.addEvent(testEvent(DepositMethod, asset, 1, asset))
+	r.check(models.Account{Amount: config.InitialMbr, MinBalance: config.InitialMbr})
+	if !r.Assets[1].Overflow || len(r.Discrepancies) != 1 {
+		t.Fatalf("expected an overflow, got %+v, discrepancies %v", r.Assets[1],
+			r.Discrepancies)
+	}
+}
@@ -0,0 +1,317 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"encoding/binary"
+	"fmt"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/config"
+)
+
+// arc4ReturnPrefix is the prefix of the log carrying the return value of an arc4 method
+var arc4ReturnPrefix = []byte{0x15, 0x1f, 0x7c, 0x75}
+
+// TreeEvent is a deposit, withdrawal, transfer or join-split read from the chain
+type TreeEvent struct {
+	Call   AppCall
+	// Method is DepositMethod, WithDrawalMethod, AddressWithdrawalMethod, TransferMethod
+	// or JoinSplitMethod
+	Method string
+
+	// PublicInputs are the public inputs of the zk-proof, see the APP methods
+	// for their order
+	PublicInputs [][]byte
+	// NoChange is the `no_change` argument of a withdrawal, always false for transfers
+	// and join-splits
+	NoChange bool
+	// AssetId is the asset of the deposit or withdrawal, 0 for Algo
+	AssetId uint64
+
+	// LeafIndex and Root are the values returned by the method
+	LeafIndex uint64
+	Root      []byte
+	// Commitments are the commitments inserted in the tree, in insertion order
+	Commitments [][]byte
+}
+
+// Scanner rebuilds the commitment tree of the app from its on-chain history.
+// Each call to Scan reads the new deposits and withdrawals from the source,
+// inserts their commitments in the tree, checks the roots against the ones
+// returned by the contract and stored in the `roots` box, and then updates the
+// Frontend tree.
+type Scanner struct {
+	f      *Frontend
+	source TxnSource
+	tree   *Tree
+
+	// roots mirrors the `roots` box, rootsAdded counts the roots added to it
+	roots      [config.RootsCount][]byte
+	rootsAdded uint64
+
+	depositSelector           []byte
+	withdrawalSelector        []byte
+	addressWithdrawalSelector []byte
+	transferSelector          []byte
+	joinSplitSelector         []byte
+
+	// LastRound is the last round scanned, 0 if nothing was scanned yet
+	LastRound uint64
+}
+
+// NewScanner returns a Scanner for the frontend app reading from source
+func (f *Frontend) NewScanner(source TxnSource) (*Scanner, error) {
+	depositMethod, err := f.App.Schema.Contract.GetMethodByName(DepositMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", DepositMethod, err)
+	}
+	withdrawalMethod, err := f.App.Schema.Contract.GetMethodByName(WithDrawalMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", WithDrawalMethod, err)
+	}
+	addressWithdrawalMethod, err := f.App.Schema.Contract.GetMethodByName(AddressWithdrawalMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", AddressWithdrawalMethod, err)
+	}
+	transferMethod, err := f.App.Schema.Contract.GetMethodByName(TransferMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", TransferMethod, err)
+	}
+	joinSplitMethod, err := f.App.Schema.Contract.GetMethodByName(JoinSplitMethod)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get method %s: %v", JoinSplitMethod, err)
+	}
+	s := &Scanner{
+		f:                         f,
+		source:                    source,
+		tree:                      NewTree(f.App.TreeConfig),
+		depositSelector:           depositMethod.GetSelector(),
+		withdrawalSelector:        withdrawalMethod.GetSelector(),
+		addressWithdrawalSelector: addressWithdrawalMethod.GetSelector(),
+		transferSelector:          transferMethod.GetSelector(),
+		joinSplitSelector:         joinSplitMethod.GetSelector(),
+	}
+	// the init method adds the root of the empty tree
+	s.addRoot(s.tree.Root())
+	return s, nil
+}
+
+// Scan reads the chain history since the last scan and returns the new events.
+// On success the Frontend tree is replaced with the rebuilt tree.
+func (s *Scanner) Scan(ctx context.Context) ([]*TreeEvent, error) {
+	minRound := s.f.App.CreationBlock
+	if s.LastRound >= minRound {
+		minRound = s.LastRound + 1
+	}
+	calls, lastRound, err := s.source.AppCalls(ctx, s.f.App.Id, minRound)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read app calls: %v", err)
+	}
+
+	var events []*TreeEvent
+	for _, call := range calls {
+		event, err := s.parseEvent(call)
+		if err != nil {
+			return nil, fmt.Errorf("failed to parse transaction %s: %v", call.TxID, err)
+		}
+		if event == nil {
+			continue
+		}
+		if err := s.apply(event); err != nil {
+			return nil, fmt.Errorf("failed to apply transaction %s: %v", call.TxID, err)
+		}
+		events = append(events, event)
+	}
+
+	if err := s.checkRootsBox(ctx); err != nil {
+		return nil, err
+	}
+
+	if lastRound > s.LastRound {
+		s.LastRound = lastRound
+	}
+	// the frontend gets a copy so that its own insertions do not affect the scanner
+	s.f.Tree = s.tree.Clone()
+
+	return events, nil
+}
+
+// parseEvent decodes a deposit, withdrawal, transfer or join-split app call, it
+// returns nil for other calls
+func (s *Scanner) parseEvent(call AppCall) (*TreeEvent, error) {
+	if len(call.Args) == 0 {
+		return nil, nil
+	}
+	event := &TreeEvent{Call: call}
+	switch {
+	case bytes.Equal(call.Args[0], s.depositSelector):
+		event.Method = DepositMethod
+	case bytes.Equal(call.Args[0], s.withdrawalSelector):
+		event.Method = WithDrawalMethod
+	case bytes.Equal(call.Args[0], s.addressWithdrawalSelector):
+		event.Method = AddressWithdrawalMethod
+	case bytes.Equal(call.Args[0], s.transferSelector):
+		event.Method = TransferMethod
+	case bytes.Equal(call.Args[0], s.joinSplitSelector):
+		event.Method = JoinSplitMethod
+	default:
+		return nil, nil
+	}
+
+	// args: selector, proof, public inputs, ...
+	if len(call.Args) < 3 {
+		return nil, fmt.Errorf("missing arguments")
+	}
+	publicInputs, err := decodeBytes32Array(call.Args[2])
+	if err != nil {
+		return nil, fmt.Errorf("failed to decode public inputs: %v", err)
+	}
+	event.PublicInputs = publicInputs
+
+	if len(call.Logs) == 0 {
+		return nil, fmt.Errorf("missing return value")
+	}
+	event.LeafIndex, event.Root, err = decodeReturnValue(call.Logs[len(call.Logs)-1])
+	if err != nil {
+		return nil, err
+	}
+
+	switch event.Method {
+	case DepositMethod:
+		// public inputs: amount, asset_id, commitment
+		if len(publicInputs) != 3 {
+			return nil, fmt.Errorf("wrong number of deposit public inputs: %d",
+				len(publicInputs))
+		}
+		event.AssetId = binary.BigEndian.Uint64(publicInputs[1][24:])
+		event.Commitments = [][]byte{publicInputs[2]}
+	case WithDrawalMethod, AddressWithdrawalMethod:
+		// args: selector, proof, public inputs, recipient, fee_recipient, no_change,
+		// and owner for address withdrawals
+		// public inputs: recipient_mod, withdrawal, fee, asset_id, nullifier, root,
+		// unspent_commitment, spent_commitment, and owner_mod for address withdrawals
+		inputsCount := 8
+		if event.Method == AddressWithdrawalMethod {
+			inputsCount = 9
+		}
+		if len(call.Args) < 6 {
+			return nil, fmt.Errorf("missing withdrawal arguments")
+		}
+		if len(publicInputs) != inputsCount {
+			return nil, fmt.Errorf("wrong number of withdrawal public inputs: %d",
+				len(publicInputs))
+		}
+		event.AssetId = binary.BigEndian.Uint64(publicInputs[3][24:])
+		event.NoChange = len(call.Args[5]) == 1 && call.Args[5][0]&0x80 != 0
+		if !event.NoChange {
+			event.Commitments = [][]byte{publicInputs[6], publicInputs[7]}
+		}
+	case TransferMethod:
+		// args: selector, proof, public inputs, fee_recipient
+		// public inputs: fee, asset_id, nullifier, root, unspent_commitment,
+		// spent_commitment
+		if len(publicInputs) != 6 {
+			return nil, fmt.Errorf("wrong number of transfer public inputs: %d",
+				len(publicInputs))
+		}
+		event.AssetId = binary.BigEndian.Uint64(publicInputs[1][24:])
+		event.Commitments = [][]byte{publicInputs[4], publicInputs[5]}
+	case JoinSplitMethod:
+		// args: selector, proof, public inputs, fee_recipient
+		// public inputs: fee, asset_id, root, nullifiers..., commitments...
+		if len(publicInputs) != 3+circuits.JoinSplitInputs+circuits.JoinSplitOutputs {
+			return nil, fmt.Errorf("wrong number of join-split public inputs: %d",
+				len(publicInputs))
+		}
+		event.AssetId = binary.BigEndian.Uint64(publicInputs[1][24:])
+		event.Commitments = publicInputs[3+circuits.JoinSplitInputs:]
+	}
+	return event, nil
+}
+
+// EncryptedNotes returns the encrypted notes carried in the event note field for
+// each of its commitments, in the same order: see ParseSpendNotes for the outputs of
+// withdrawals and transfers. Missing or malformed notes are nil, e.g. the payment
+// note of withdrawals sent before it was encrypted.
+func (e *TreeEvent) EncryptedNotes() []*EncryptedNote {
+	notes := make([]*EncryptedNote, len(e.Commitments))
+	parsed, err := ParseEncryptedNotes(e.Call.Note)
+	if err != nil {
+		return notes
+	}
+	copy(notes, parsed)
+	return notes
+}
+
+// apply inserts the event commitments in the tree and checks the returned root
+func (s *Scanner) apply(event *TreeEvent) error {
+	if len(event.Commitments) == 0 {
+		return nil
+	}
+	if event.LeafIndex != uint64(s.tree.LeafCount()) {
+		return fmt.Errorf("leaf index mismatch: contract %d, local %d",
+			event.LeafIndex, s.tree.LeafCount())
+	}
+	for _, commitment := range event.Commitments {
+		s.tree.AddLeaf(commitment)
+		s.addRoot(s.tree.Root())
+	}
+	if !bytes.Equal(event.Root, s.tree.Root()) {
+		return fmt.Errorf("root mismatch: contract %x, local %x", event.Root, s.tree.Root())
+	}
+	return nil
+}
+
+// addRoot adds root to the local mirror of the `roots` box
+func (s *Scanner) addRoot(root []byte) {
+	s.roots[s.rootsAdded%config.RootsCount] = root
+	s.rootsAdded++
+}
+
+// checkRootsBox checks the local roots against the `roots` box of the app
+func (s *Scanner) checkRootsBox(ctx context.Context) error {
+	box, err := s.f.algod.GetApplicationBoxByName(s.f.App.Id, []byte("roots")).Do(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to read roots box: %v", err)
+	}
+	if len(box.Value) != 32*config.RootsCount {
+		return fmt.Errorf("unexpected roots box size: %d", len(box.Value))
+	}
+	for i, root := range s.roots {
+		if root == nil {
+			continue
+		}
+		if !bytes.Equal(root, box.Value[i*32:(i+1)*32]) {
+			return fmt.Errorf("root %d does not match the roots box, the chain may "+
+				"have advanced during the scan, try scanning again", i)
+		}
+	}
+	return nil
+}
+
+// decodeBytes32Array decodes an arc4 byte[32][]
+func decodeBytes32Array(b []byte) ([][]byte, error) {
+	if len(b) < 2 {
+		return nil, fmt.Errorf("array too short")
+	}
+	n := int(binary.BigEndian.Uint16(b[:2]))
+	if len(b) != 2+32*n {
+		return nil, fmt.Errorf("array length mismatch: %d elements in %d bytes", n, len(b))
+	}
+	res := make([][]byte, n)
+	for i := range n {
+		res[i] = b[2+32*i : 2+32*(i+1)]
+	}
+	return res, nil
+}
+
+// decodeReturnValue decodes the (uint64,byte[32]) logged by a deposit or withdrawal
+func decodeReturnValue(log []byte) (uint64, []byte, error) {
+	if len(log) != len(arc4ReturnPrefix)+8+32 ||
+		!bytes.Equal(log[:len(arc4ReturnPrefix)], arc4ReturnPrefix) {
+		return 0, nil, fmt.Errorf("invalid return value")
+	}
+	value := log[len(arc4ReturnPrefix):]
+	return binary.BigEndian.Uint64(value[:8]), value[8:], nil
+}
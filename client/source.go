@@ -0,0 +1,140 @@
+package client
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/algod"
+	"github.com/algorand/go-algorand-sdk/v2/client/v2/indexer"
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// AppCall is a top level application call read from the chain
+type AppCall struct {
+	Round    uint64
+	TxID     string
+	Sender   string
+	Args     [][]byte
+	Accounts []string
+	Note     []byte
+	Logs     [][]byte
+}
+
+// TxnSource provides the on-chain history of an application
+type TxnSource interface {
+	// AppCalls returns the top level calls to appId confirmed from minRound onward,
+	// in confirmation order, and the last round covered by the result.
+	AppCalls(ctx context.Context, appId uint64, minRound uint64) ([]AppCall, uint64, error)
+}
+
+// IndexerSource reads the application history from an indexer
+type IndexerSource struct {
+	Client *indexer.Client
+}
+
+// AppCalls implements TxnSource
+func (s *IndexerSource) AppCalls(ctx context.Context, appId uint64, minRound uint64) (
+	[]AppCall, uint64, error) {
+
+	var calls []AppCall
+	var lastRound uint64
+	nextToken := ""
+	for {
+		query := s.Client.SearchForTransactions().ApplicationId(appId).
+			TxType("appl").MinRound(minRound)
+		if lastRound > 0 {
+			// pin the following pages to the round of the first one
+			query = query.MaxRound(lastRound)
+		}
+		if nextToken != "" {
+			query = query.NextToken(nextToken)
+		}
+		res, err := query.Do(ctx)
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to search transactions: %v", err)
+		}
+		if lastRound == 0 {
+			lastRound = res.CurrentRound
+		}
+		for _, txn := range res.Transactions {
+			if txn.ConfirmedRound > lastRound ||
+				txn.ApplicationTransaction.ApplicationId != appId {
+				continue
+			}
+			calls = append(calls, AppCall{
+				Round:    txn.ConfirmedRound,
+				TxID:     txn.Id,
+				Sender:   txn.Sender,
+				Args:     txn.ApplicationTransaction.ApplicationArgs,
+				Accounts: txn.ApplicationTransaction.Accounts,
+				Note:     txn.Note,
+				Logs:     txn.Logs,
+			})
+		}
+		if res.NextToken == "" || len(res.Transactions) == 0 {
+			break
+		}
+		nextToken = res.NextToken
+	}
+	return calls, lastRound, nil
+}
+
+// BlockSource reads the application history walking the blocks of an algod node,
+// e.g. a local node without an indexer.
+// The node must have the blocks from minRound onward (i.e., an archival node for
+// a scan from the creation block of a long lived application).
+type BlockSource struct {
+	Client *algod.Client
+}
+
+// AppCalls implements TxnSource
+func (s *BlockSource) AppCalls(ctx context.Context, appId uint64, minRound uint64) (
+	[]AppCall, uint64, error) {
+
+	status, err := s.Client.Status().Do(ctx)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get node status: %v", err)
+	}
+	lastRound := status.LastRound
+
+	var calls []AppCall
+	for round := minRound; round <= lastRound; round++ {
+		block, err := s.Client.Block(round).Do(ctx)
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to get block %d: %v", round, err)
+		}
+		for _, stib := range block.Payset {
+			txn := stib.SignedTxn.Txn
+			if txn.Type != types.ApplicationCallTx ||
+				uint64(txn.ApplicationID) != appId {
+				continue
+			}
+			// the genesis fields are omitted in blocks, restore them to get the txid
+			if stib.HasGenesisID {
+				txn.GenesisID = block.GenesisID
+			}
+			if stib.HasGenesisHash {
+				txn.GenesisHash = block.GenesisHash
+			}
+			accounts := make([]string, len(txn.Accounts))
+			for i, a := range txn.Accounts {
+				accounts[i] = a.String()
+			}
+			logs := make([][]byte, len(stib.EvalDelta.Logs))
+			for i, l := range stib.EvalDelta.Logs {
+				logs[i] = []byte(l)
+			}
+			calls = append(calls, AppCall{
+				Round:    round,
+				TxID:     crypto.GetTxID(txn),
+				Sender:   txn.Sender.String(),
+				Args:     txn.ApplicationArgs,
+				Accounts: accounts,
+				Note:     txn.Note,
+				Logs:     logs,
+			})
+		}
+	}
+	return calls, lastRound, nil
+}
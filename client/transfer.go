@@ -0,0 +1,122 @@
+package client
+
+import (
+	"fmt"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+type TransferOpts struct {
+	FeeRecipient types.Address
+	FeeSigner    transaction.TransactionSigner
+	Amount       uint64 // sent to the receiver
+	Fee          uint64
+	FromNote     *Note
+}
+
+// SendTransfer spends FromNote into a note of Amount owned by receiverPubkey and a
+// change note, and sends the transfer to the network. Nothing leaves the app but
+// the fee, which defaults as in SendWithdrawal.
+// Both notes are encrypted in the note field of the transfer app call, the change
+// note first (see EncryptedNotesBytes), so that the receiver can discover its note.
+func (f *Frontend) SendTransfer(opts *TransferOpts, spenderPrivkey *eddsa.PrivateKey,
+	receiverPubkey eddsa.PublicKey) (*Transfer, error) {
+
+	if err := f.App.Supports(TransferMethod); err != nil {
+		return nil, err
+	}
+	fromNote := opts.FromNote
+	g, err := f.newSpendGroup(fromNote, 1, opts.Fee, opts.FeeRecipient, opts.FeeSigner)
+	if err != nil {
+		return nil, err
+	}
+	assetId := fromNote.AssetId
+
+	if opts.Amount > fromNote.Amount || g.fee > fromNote.Amount-opts.Amount {
+		return nil, fmt.Errorf("note amount %d cannot cover %d and fee %d",
+			fromNote.Amount, opts.Amount, g.fee)
+	}
+	unspent := fromNote.Amount - opts.Amount - g.fee
+	unspentNote, encryptedUnspentNote, err := f.NewAssetNote(unspent, assetId, *spenderPrivkey,
+		ReceivingKey(*spenderPrivkey))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create change note: %v", err)
+	}
+	sentNote, encryptedSentNote, err := f.NewAssetNote(opts.Amount, assetId, *spenderPrivkey,
+		receiverPubkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create sent note: %v", err)
+	}
+
+	index, path, root, err := f.spendInputs(fromNote)
+	if err != nil {
+		return nil, err
+	}
+	nk := NewNullifierKey(*spenderPrivkey)
+	nullifier := f.MakeNullifier(fromNote, nk)
+
+	circuitSig, err := signSpend(spenderPrivkey, uint64ToBytes32(g.fee),
+		uint64ToBytes32(assetId), unspentNote.Commitment, sentNote.Commitment)
+	if err != nil {
+		return nil, err
+	}
+
+	inputX := spenderPrivkey.PublicKey.A.X.Bytes()
+	inputY := spenderPrivkey.PublicKey.A.Y.Bytes()
+
+	g.assignment = &circuits.TransferCircuit{
+		Fee:               g.fee,
+		AssetId:           assetId,
+		Nullifier:         nullifier,
+		Root:              root,
+		UnspentCommitment: unspentNote.Commitment,
+		SpentCommitment:   sentNote.Commitment,
+		SpenderX:          inputX[:],
+		SpenderY:          inputY[:],
+		NullifierKey:      []byte(nk),
+		Signature:         circuitSig,
+		OutputX:           sentNote.OutputX,
+		OutputY:           sentNote.OutputY,
+		SpendableK:        fromNote.K,
+		SpendableR:        fromNote.R,
+		SpendableAmount:   fromNote.Amount,
+		SpendableIndex:    index,
+		SpendablePath:     path,
+		SpentAmount:       sentNote.Amount,
+		SpentK:            sentNote.K,
+		SpentR:            sentNote.R,
+		UnspentAmount:     unspentNote.Amount,
+		UnspentK:          unspentNote.K,
+		UnspentR:          unspentNote.R,
+	}
+	g.method = TransferMethod
+	g.verifier = f.App.TransferVerifier
+	g.args = []interface{}{g.feeRecipient[:]}
+	g.accounts = []string{g.feeRecipient.String()}
+	g.nullifiers = [][]byte{nullifier}
+	g.note = EncryptedNotesBytes(encryptedUnspentNote, encryptedSentNote)
+
+	res, changeIndex, err := f.sendSpendGroup(g)
+	if err != nil {
+		return nil, err
+	}
+
+	unspentNote.InsertedIndex = int(changeIndex)
+	sentNote.InsertedIndex = int(changeIndex) + 1
+	f.Tree.AddLeaf(unspentNote.Commitment)
+	f.Tree.AddLeaf(sentNote.Commitment)
+
+	t := &Transfer{
+		TxnIds:   res.TxIDs,
+		Note:     unspentNote,
+		SentNote: sentNote,
+	}
+
+	f.Transfers = append(f.Transfers, t)
+
+	return t, nil
+}
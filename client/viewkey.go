@@ -0,0 +1,119 @@
+package client
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// ViewedNote is a note recovered with a view key.
+// The view key holder learns the note secrets, so it can verify the commitment
+// and compute the nullifier, but cannot spend the note which requires a signature
+// of the Receiver private key, or the authorization of the ReceiverAddress.
+type ViewedNote struct {
+	*Note
+	// Sender is the public key of the note creator, SenderAddress is set instead
+	// if the creator is an Algorand address
+	Sender        eddsa.PublicKey
+	SenderAddress types.Address
+	// Receiver is the public key owning the note, ReceiverAddress is set instead
+	// if the owner is an Algorand address
+	Receiver        eddsa.PublicKey
+	ReceiverAddress types.Address
+	// Event is the app call which inserted the note, nil if the note was not
+	// recovered from an event
+	Event *TreeEvent
+	// Spent is true if the note nullifier was used, set by AuditHistory if
+	// NullifierKnown, i.e. the nullifier key of the receiver was given to it or the
+	// receiver is an address
+	Spent          bool
+	NullifierKnown bool
+}
+
+// viewNote returns the note carried in the view field of encryptedNote
+func (n *EncryptedNote) viewNote() (*EncryptedNote, error) {
+	if n.Version == EncryptedNoteLegacy || len(n.View) != viewFieldSize {
+		return nil, fmt.Errorf("note has no view field")
+	}
+	return &EncryptedNote{
+		Version:         n.Version,
+		EphemeralPubkey: n.View[:ephemeralPubkeySize],
+		Payload:         n.View[ephemeralPubkeySize:],
+	}, nil
+}
+
+// RecoverViewedNote decrypts the view field of encryptedNote with viewPrivkey and
+// reconstructs the note with its sender and receiver
+func (f *Frontend) RecoverViewedNote(encryptedNote *EncryptedNote, viewPrivkey eddsa.PrivateKey,
+	insertedIndex int) (*ViewedNote, error) {
+
+	viewNote, err := encryptedNote.viewNote()
+	if err != nil {
+		return nil, err
+	}
+	secrets, err := decryptNote(viewNote, viewPrivkey)
+	if err != nil {
+		return nil, err
+	}
+	note, err := f.noteFromSecrets(secrets, insertedIndex)
+	if err != nil {
+		return nil, err
+	}
+	viewed := &ViewedNote{Note: note, ReceiverAddress: note.OwnerAddress}
+	if secrets.inputIsAddress {
+		copy(viewed.SenderAddress[:], secrets.inputPubkey)
+	} else if _, err := viewed.Sender.SetBytes(secrets.inputPubkey); err != nil {
+		return nil, fmt.Errorf("failed to read sender public key: %v", err)
+	}
+	if !secrets.outputIsAddress {
+		if _, err := viewed.Receiver.SetBytes(secrets.outputPubkey); err != nil {
+			return nil, fmt.Errorf("failed to read receiver public key: %v", err)
+		}
+	}
+	return viewed, nil
+}
+
+// AuditHistory recovers with viewPrivkey every note of events encrypted to the
+// view key, in insertion order, and checks whether they were spent: the nullifiers
+// of notes owned by a public key are only known with its key in nullifierKeys, by
+// ReceivingKey, which receivers share with the auditor (see NewNullifierKey).
+// Events are the ones returned by a Scanner, for a complete history they must
+// cover the app history from its creation.
+func (f *Frontend) AuditHistory(ctx context.Context, events []*TreeEvent,
+	viewPrivkey eddsa.PrivateKey, nullifierKeys map[eddsa.PublicKey]NullifierKey) (
+	[]*ViewedNote, error) {
+
+	var history []*ViewedNote
+	for _, event := range events {
+		for i, encryptedNote := range event.EncryptedNotes() {
+			if encryptedNote == nil || len(encryptedNote.View) == 0 {
+				continue
+			}
+			viewed, err := f.RecoverViewedNote(encryptedNote, viewPrivkey,
+				int(event.LeafIndex)+i)
+			if err != nil {
+				continue
+			}
+			// the view field of a note can be forged, only trust it if it opens the
+			// inserted commitment
+			if !bytes.Equal(viewed.Commitment, event.Commitments[i]) {
+				continue
+			}
+			viewed.Event = event
+
+			nk, ok := nullifierKeys[viewed.Receiver]
+			viewed.NullifierKnown = ok || !viewed.ReceiverAddress.IsZero()
+			if viewed.NullifierKnown {
+				viewed.Spent, err = f.IsSpent(ctx, viewed.Note, nk)
+				if err != nil {
+					return nil, err
+				}
+			}
+			history = append(history, viewed)
+		}
+	}
+	return history, nil
+}
@@ -0,0 +1,554 @@
+package client
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"github.com/consensys/gnark-crypto/ecc/twistededwards"
+	"github.com/consensys/gnark-crypto/hash"
+	"github.com/consensys/gnark/frontend"
+	sigEddsa "github.com/consensys/gnark/std/signature/eddsa"
+)
+
+type WithdrawalOpts struct {
+	Recipient    types.Address
+	FeeRecipient types.Address
+	FeeSigner    transaction.TransactionSigner
+	Amount       uint64
+	Fee          uint64
+	NoChange     bool
+	FromNote     *Note
+	SpendAmount  uint64 // kept in a note owned by the output key
+}
+
+// AddressOwner is an Algorand address owning notes
+type AddressOwner struct {
+	Address types.Address
+	// Signer signs for Address a transaction authorizing the withdrawal group
+	Signer transaction.TransactionSigner
+	// NotePubkey is the ReceivingKey of the key the owner uses to discover its
+	// notes, the change notes are encrypted to it
+	NotePubkey eddsa.PublicKey
+}
+
+// spendGroup holds what differs between the groups spending a note: withdrawals of
+// notes owned by a public key or by an Algorand address, and transfers
+type spendGroup struct {
+	method     string
+	verifier   *Lsig
+	assignment frontend.Circuit
+	args       []interface{} // method arguments after the proof and public inputs
+	accounts   []string      // foreign accounts
+	nullifiers [][]byte
+	assetId    uint64
+
+	fee          uint64
+	feeRecipient types.Address
+	feeSigner    transaction.TransactionSigner
+
+	// owner, if set, signs a transaction of the group to authorize it
+	owner *AddressOwner
+
+	// note is the note field of the app call, with the encrypted output notes
+	note []byte
+}
+
+// SendWithdrawal creates a withdrawal transaction and sends it to the network.
+// If fee is 0, the fee will be set to the default withdrawal fee.
+// If feeRecipient or feeSigner are not set, the fee will be sent to the TSS account
+// and the TSS used to sign the transaction.
+// If noChange is true, no change will be added to the tree (to be used when the
+// tree is full, otherwise the withdrawal will fail).
+// The withdrawal is in the asset of the note. For ASA notes the fee is in the asset
+// and can be 0, and the feeRecipient must be set since it pays the transaction fees
+// and the nullifier MBR in Algo; recipient and feeRecipient must be opted in the asset.
+// The change note is encrypted in the note field of the app call followed, if
+// SpendAmount is not 0, by the note of SpendAmount owned by outputPubkey so that its
+// owner can discover it (see ParseSpendNotes).
+func (f *Frontend) SendWithdrawal(opts *WithdrawalOpts, spenderPrivkey *eddsa.PrivateKey, outputPubkey eddsa.PublicKey) (*Withdrawal, error) {
+	g, err := f.newSpendGroup(opts.FromNote, 1, opts.Fee, opts.FeeRecipient, opts.FeeSigner)
+	if err != nil {
+		return nil, err
+	}
+	unspentNote, spendNote, err := f.assignWithdrawal(g, opts, spenderPrivkey, outputPubkey)
+	if err != nil {
+		return nil, err
+	}
+	return f.sendWithdrawal(opts, g, unspentNote, spendNote)
+}
+
+// assignWithdrawal creates the output notes of the withdrawal of opts and sets the
+// assignment, nullifier and note field of g. It returns the change and spent notes.
+func (f *Frontend) assignWithdrawal(g *spendGroup, opts *WithdrawalOpts,
+	spenderPrivkey *eddsa.PrivateKey, outputPubkey eddsa.PublicKey) (*Note, *Note, error) {
+
+	fromNote := opts.FromNote
+	withdrawalAmount, spendAmount, assetId := opts.Amount, opts.SpendAmount, fromNote.AssetId
+
+	unspent, err := unspentAmount(fromNote, withdrawalAmount, spendAmount, g.fee)
+	if err != nil {
+		return nil, nil, err
+	}
+	unspentNote, encryptedUnspentNote, err := f.NewAssetNote(unspent, assetId, *spenderPrivkey,
+		ReceivingKey(*spenderPrivkey))
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to create change note: %v", err)
+	}
+	unspentCommitment := unspentNote.Commitment
+
+	spendNote, encryptedSpendNote, err := f.NewAssetNote(spendAmount, assetId, *spenderPrivkey,
+		outputPubkey)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to create spent note: %v", err)
+	}
+	spendCommitment := spendNote.Commitment
+
+	index, path, root, err := f.spendInputs(fromNote)
+	if err != nil {
+		return nil, nil, err
+	}
+	nk := NewNullifierKey(*spenderPrivkey)
+	nullifier := f.MakeNullifier(fromNote, nk)
+
+	circuitSig, err := signSpend(spenderPrivkey, addressMod(opts.Recipient),
+		uint64ToBytes32(withdrawalAmount), uint64ToBytes32(g.fee), uint64ToBytes32(assetId),
+		unspentCommitment, spendCommitment)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	inputX := spenderPrivkey.PublicKey.A.X.Bytes()
+	inputY := spenderPrivkey.PublicKey.A.Y.Bytes()
+	outputX := outputPubkey.A.X.Bytes()
+	outputY := outputPubkey.A.Y.Bytes()
+
+	g.assignment = &circuits.WithdrawalCircuit{
+		WithdrawalAddress: opts.Recipient[:],
+		WithdrawalAmount:  withdrawalAmount,
+		Fee:               g.fee,
+		AssetId:           assetId,
+		UnspentCommitment: unspentCommitment,
+		Nullifier:         nullifier,
+		Root:              root,
+		SpendableK:        fromNote.K,
+		SpendableR:        fromNote.R,
+		SpendableAmount:   fromNote.Amount,
+		UnspentAmount:     unspentNote.Amount,
+		UnspentK:          unspentNote.K,
+		UnspentR:          unspentNote.R,
+		SpendableIndex:    index,
+		SpendablePath:     path,
+		SpenderX:          inputX[:],
+		SpenderY:          inputY[:],
+		NullifierKey:      []byte(nk),
+		Signature:         circuitSig,
+		OutputX:           outputX[:],
+		OutputY:           outputY[:],
+		SpentAmount:       spendNote.Amount,
+		SpentK:            spendNote.K,
+		SpentR:            spendNote.R,
+		SpentCommitment:   spendCommitment,
+	}
+	g.method = WithDrawalMethod
+	g.verifier = f.App.WithdrawalVerifier
+	g.nullifiers = [][]byte{nullifier}
+	g.note = encryptedUnspentNote.Bytes()
+	if spendAmount > 0 {
+		g.note = EncryptedNotesBytes(encryptedUnspentNote, encryptedSpendNote)
+	}
+
+	return unspentNote, spendNote, nil
+}
+
+// SendAddressWithdrawal creates a withdrawal transaction of a note owned by an
+// Algorand address and sends it to the network. The owner signs a transaction of the
+// group to authorize the withdrawal, the change note stays owned by it and the spent
+// note is owned by outputPubkey. The options are as in SendWithdrawal.
+func (f *Frontend) SendAddressWithdrawal(opts *WithdrawalOpts, owner *AddressOwner,
+	outputPubkey eddsa.PublicKey) (*Withdrawal, error) {
+
+	if err := f.App.Supports(AddressWithdrawalMethod); err != nil {
+		return nil, err
+	}
+	fromNote := opts.FromNote
+	if fromNote.OwnerAddress != owner.Address {
+		return nil, fmt.Errorf("note not owned by %s", owner.Address)
+	}
+	g, err := f.newSpendGroup(fromNote, 1, opts.Fee, opts.FeeRecipient, opts.FeeSigner)
+	if err != nil {
+		return nil, err
+	}
+	assetId := fromNote.AssetId
+
+	unspent, err := unspentAmount(fromNote, opts.Amount, opts.SpendAmount, g.fee)
+	if err != nil {
+		return nil, err
+	}
+	unspentNote, encryptedUnspentNote, err := f.NewAddressNote(unspent, assetId,
+		owner.Address, owner.Address, owner.NotePubkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create change note: %v", err)
+	}
+
+	spendNote, encryptedSpendNote, err := f.newNote(&noteSecrets{
+		amount:         opts.SpendAmount,
+		assetId:        assetId,
+		outputPubkey:   outputPubkey.Bytes(),
+		inputPubkey:    owner.Address[:],
+		inputIsAddress: true,
+	}, outputPubkey)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create spent note: %v", err)
+	}
+
+	index, path, root, err := f.spendInputs(fromNote)
+	if err != nil {
+		return nil, err
+	}
+	nullifier := f.MakeNullifier(fromNote, nil)
+	_, ownerMod := AddressOwnerCoordinates(owner.Address)
+
+	g.assignment = &circuits.AddressWithdrawalCircuit{
+		WithdrawalAddress: opts.Recipient[:],
+		WithdrawalAmount:  opts.Amount,
+		Fee:               g.fee,
+		AssetId:           assetId,
+		UnspentCommitment: unspentNote.Commitment,
+		Nullifier:         nullifier,
+		Root:              root,
+		OwnerAddress:      ownerMod,
+		SpendableK:        fromNote.K,
+		SpendableR:        fromNote.R,
+		SpendableAmount:   fromNote.Amount,
+		UnspentAmount:     unspentNote.Amount,
+		UnspentK:          unspentNote.K,
+		UnspentR:          unspentNote.R,
+		SpendableIndex:    index,
+		SpendablePath:     path,
+		OutputX:           spendNote.OutputX,
+		OutputY:           spendNote.OutputY,
+		SpentAmount:       spendNote.Amount,
+		SpentK:            spendNote.K,
+		SpentR:            spendNote.R,
+		SpentCommitment:   spendNote.Commitment,
+	}
+	g.method = AddressWithdrawalMethod
+	g.verifier = f.App.AddressWithdrawalVerifier
+	g.args = []interface{}{owner.Address[:]}
+	g.nullifiers = [][]byte{nullifier}
+	g.owner = owner
+	g.note = encryptedUnspentNote.Bytes()
+	if opts.SpendAmount > 0 {
+		g.note = EncryptedNotesBytes(encryptedUnspentNote, encryptedSpendNote)
+	}
+
+	return f.sendWithdrawal(opts, g, unspentNote, spendNote)
+}
+
+// sendWithdrawal sends the withdrawal group g and adds its output notes to the tree
+// unless NoChange is set
+func (f *Frontend) sendWithdrawal(opts *WithdrawalOpts, g *spendGroup, unspentNote,
+	spendNote *Note) (*Withdrawal, error) {
+
+	g.setWithdrawalArgs(opts.Recipient, opts.NoChange)
+	res, changeIndex, err := f.sendSpendGroup(g)
+	if err != nil {
+		return nil, err
+	}
+
+	w := &Withdrawal{
+		ToAddress: opts.Recipient.String(),
+		TxnIds:    res.TxIDs,
+		Note:      unspentNote,
+		SpentNote: spendNote,
+	}
+	f.addWithdrawal(w, opts.NoChange, changeIndex)
+
+	return w, nil
+}
+
+// setWithdrawalArgs prepends the recipient, fee recipient and no change arguments of
+// the withdrawal methods to the arguments of g
+func (g *spendGroup) setWithdrawalArgs(recipient types.Address, noChange bool) {
+	g.args = append([]interface{}{recipient[:], g.feeRecipient[:], noChange}, g.args...)
+	g.accounts = []string{g.feeRecipient.String(), recipient.String()}
+}
+
+// addWithdrawal adds the output notes of w to the tree at changeIndex unless noChange
+// is set, and w to the frontend withdrawals
+func (f *Frontend) addWithdrawal(w *Withdrawal, noChange bool, changeIndex uint64) {
+	if !noChange {
+		w.Note.InsertedIndex = int(changeIndex)
+		w.SentNote.InsertedIndex = int(changeIndex) + 1
+		f.Tree.AddLeaf(w.Note.Commitment)
+		f.Tree.AddLeaf(w.SentNote.Commitment)
+	}
+	f.Withdrawals = append(f.Withdrawals, w)
+}
+
+// defaultSpendFee returns the fee of a spend of nullifiers Algo notes when none is
+// given, the transaction fees and the MBR of the nullifiers
+func defaultSpendFee(nullifiers int) uint64 {
+	return config.WithdrawalMinFeeMultiplier*transaction.MinTxnFee +
+		uint64(nullifiers)*config.NullifierMbr
+}
+
+// checkSpendFee returns an error if fee, for a spend of nullifiers notes of assetId,
+// does not cover the MBR of the nullifiers and the minimum transaction fee, which
+// the fees of Algo notes must
+func checkSpendFee(assetId uint64, nullifiers int, fee uint64) error {
+	if assetId != 0 {
+		return nil
+	}
+	minFee := uint64(nullifiers)*config.NullifierMbr + transaction.MinTxnFee
+	if fee < minFee {
+		return fmt.Errorf("fee %d below the nullifiers MBR and transaction fee %d", fee,
+			minFee)
+	}
+	return nil
+}
+
+// unspentAmount returns the change of note after withdrawing withdrawal, spending
+// spend to the output key and paying fee
+func unspentAmount(note *Note, withdrawal, spend, fee uint64) (uint64, error) {
+	if withdrawal > note.Amount || spend > note.Amount-withdrawal ||
+		fee > note.Amount-withdrawal-spend {
+		return 0, fmt.Errorf("note amount %d cannot cover %d, %d and fee %d", note.Amount,
+			withdrawal, spend, fee)
+	}
+	return note.Amount - withdrawal - spend - fee, nil
+}
+
+// newSpendGroup returns a spendGroup spending note, with nullifiers notes in total,
+// with fee, feeRecipient and feeSigner, applying the defaults described in
+// SendWithdrawal. The fee of Algo notes must cover the MBR of the nullifiers and the
+// minimum transaction fee.
+func (f *Frontend) newSpendGroup(note *Note, nullifiers int, fee uint64,
+	feeRecipient types.Address, feeSigner transaction.TransactionSigner) (*spendGroup, error) {
+
+	g := &spendGroup{
+		assetId:      note.AssetId,
+		fee:          fee,
+		feeRecipient: feeRecipient,
+		feeSigner:    feeSigner,
+	}
+
+	if g.assetId == 0 && g.fee == 0 {
+		g.fee = defaultSpendFee(nullifiers)
+	}
+	if err := checkSpendFee(g.assetId, nullifiers, g.fee); err != nil {
+		return nil, err
+	}
+
+	if g.feeRecipient.IsZero() || g.feeSigner == nil {
+		if g.assetId != 0 {
+			return nil, fmt.Errorf("asset notes need a fee recipient and signer to be spent")
+		}
+		g.feeRecipient = f.App.TSS.Address
+		g.feeSigner = transaction.LogicSigAccountTransactionSigner{
+			LogicSigAccount: f.App.TSS.Account,
+		}
+	}
+	return g, nil
+}
+
+// spendInputs returns the index and merkle path of note in the tree and the current
+// root, to prove that note can be spent
+func (f *Frontend) spendInputs(note *Note) (int, [config.MerkleTreeLevels + 1]frontend.Variable,
+	[]byte, error) {
+
+	index, path, err := f.merklePath(note)
+	if err != nil {
+		return 0, path, nil, err
+	}
+	root, err := f.GetRoot()
+	if err != nil {
+		return 0, path, nil, fmt.Errorf("failed to get root: %v", err)
+	}
+	return index, path, root, nil
+}
+
+// merklePath returns the index and merkle path of note in the tree
+func (f *Frontend) merklePath(note *Note) (int, [config.MerkleTreeLevels + 1]frontend.Variable,
+	error) {
+
+	var path [config.MerkleTreeLevels + 1]frontend.Variable
+	if note.InsertedIndex == -1 {
+		return 0, path, fmt.Errorf("note not inserted in the tree")
+	}
+	index := note.InsertedIndex
+	leaf := f.MakeLeafValue(note)
+
+	merkleProof, err := f.Tree.CreateMerkleProof(leaf, index)
+	if err != nil {
+		return 0, path, fmt.Errorf("failed to create merkle proof: %v", err)
+	}
+	for i, v := range merkleProof {
+		path[i] = v
+	}
+	return index, path, nil
+}
+
+// signSpend signs hash(outputs...), the public outputs of a spend, with privkey and
+// returns the signature assigned for a circuit (see circuits.spendMessage)
+func signSpend(privkey *eddsa.PrivateKey, outputs ...[]byte) (sigEddsa.Signature, error) {
+	circuitSig := sigEddsa.Signature{}
+
+	hFunc := hash.MIMC_BLS12_381.New()
+	sig, err := privkey.Sign(config.Hash(outputs...), hFunc)
+	if err != nil {
+		return circuitSig, fmt.Errorf("failed to sign spend: %v", err)
+	}
+
+	circuitSig.Assign(twistededwards.BLS12_381, sig)
+	return circuitSig, nil
+}
+
+// sendSpendGroup proves the assignment of g, sends its transaction group and returns
+// the execution result and the leaf index returned by the app call
+func (f *Frontend) sendSpendGroup(g *spendGroup) (*transaction.ExecuteResult, uint64, error) {
+	args, err := f.prove(g.method, g.assignment)
+	if err != nil {
+		return nil, 0, err
+	}
+	return f.sendProvedSpendGroup(g, args)
+}
+
+// sendProvedSpendGroup sends the transaction group of g with the proof and public
+// inputs arguments proofArgs, after simulating it, and returns the execution result and
+// the leaf index returned by the app call
+func (f *Frontend) sendProvedSpendGroup(g *spendGroup, proofArgs []interface{}) (
+	*transaction.ExecuteResult, uint64, error) {
+
+	fee, feeRecipient, feeSigner := g.fee, g.feeRecipient, g.feeSigner
+	args := append(proofArgs, g.args...)
+
+	sp, err := f.algod.SuggestedParams().Do(context.Background())
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get suggested params: %v", err)
+	}
+	sp.Fee = 0
+	sp.FlatFee = true
+
+	method, err := f.App.Schema.Contract.GetMethodByName(g.method)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get method %s: %v", g.method, err)
+	}
+
+	// the app call signed by the verifier, with a box reference for each nullifier
+	var boxes []types.AppBoxReference
+	for _, nullifier := range g.nullifiers {
+		boxes = append(boxes, types.AppBoxReference{AppID: f.App.Id, Name: nullifier})
+	}
+	boxes = append(boxes,
+		types.AppBoxReference{AppID: f.App.Id, Name: []byte("subtree")},
+		types.AppBoxReference{AppID: f.App.Id, Name: []byte("roots")},
+	)
+	txnParams := transaction.AddMethodCallParams{
+		AppID:           f.App.Id,
+		Sender:          g.verifier.Address,
+		SuggestedParams: sp,
+		OnComplete:      types.NoOpOC,
+		Signer: transaction.LogicSigAccountTransactionSigner{
+			LogicSigAccount: g.verifier.Account},
+		Method:          method,
+		MethodArgs:      args,
+		ForeignAccounts: g.accounts,
+		BoxReferences:   boxes,
+		Note:            g.note,
+	}
+	if g.assetId != 0 {
+		txnParams.ForeignAssets = []uint64{g.assetId}
+	}
+
+	var atc = transaction.AtomicTransactionComposer{}
+	if err := atc.AddMethodCall(txnParams); err != nil {
+		return nil, 0, fmt.Errorf("failed to add %s method call: %v", g.method, err)
+	}
+
+	noopMethod, err := f.App.Schema.Contract.GetMethodByName(NoOpMethod)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get method %s: %v", NoOpMethod, err)
+	}
+
+	nullifiersMbr := uint64(len(g.nullifiers)) * config.NullifierMbr
+	if g.assetId == 0 {
+		sp.Fee = types.MicroAlgos(fee - nullifiersMbr)
+
+		// the transaction signed by the feeSigner (e.g., the TSS)
+		txnParams = transaction.AddMethodCallParams{
+			AppID:           f.App.Id,
+			Sender:          feeRecipient,
+			SuggestedParams: sp,
+			OnComplete:      types.NoOpOC,
+			Signer:          feeSigner,
+			Method:          noopMethod,
+			MethodArgs:      []any{0},
+		}
+
+		if err := atc.AddMethodCall(txnParams); err != nil {
+			return nil, 0, fmt.Errorf("failed to add %s method call: %v", NoOpMethod, err)
+		}
+	} else {
+		// the nullifier MBR payment signed by the feeSigner, paying the group fees
+		sp.Fee = config.WithdrawalMinFeeMultiplier * transaction.MinTxnFee
+		txn, err := transaction.MakePaymentTxn(feeRecipient.String(),
+			crypto.GetApplicationAddress(f.App.Id).String(), nullifiersMbr, nil,
+			types.ZeroAddress.String(), sp,
+		)
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to make payment txn: %v", err)
+		}
+		err = atc.AddTransaction(transaction.TransactionWithSigner{Txn: txn, Signer: feeSigner})
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to add payment txn: %v", err)
+		}
+	}
+
+	// additional transactions to meet the verifier opcode budget
+	txnNeeded := config.VerifierTopLevelTxnNeeded - 2
+	sp.Fee = 0
+
+	txnParams = transaction.AddMethodCallParams{
+		AppID:           f.App.Id,
+		Sender:          feeRecipient,
+		SuggestedParams: sp,
+		OnComplete:      types.NoOpOC,
+		Signer:          feeSigner,
+		Method:          noopMethod,
+	}
+
+	for i := range txnNeeded {
+		// the address owner authorizes the group with the last one
+		if g.owner != nil && i == txnNeeded-1 {
+			txnParams.Sender, txnParams.Signer = g.owner.Address, g.owner.Signer
+		}
+		txnParams.MethodArgs = []interface{}{i}
+		if err := atc.AddMethodCall(txnParams); err != nil {
+			return nil, 0, fmt.Errorf("failed to add %s method call: %v", NoOpMethod, err)
+		}
+	}
+
+	if err := f.simulate(&atc, g.method, txnNeeded); err != nil {
+		return nil, 0, err
+	}
+
+	res, err := atc.Execute(f.algod, context.Background(), 4)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to execute transaction: %v", err)
+	}
+	f.confirmNotes()
+
+	leafIndex, _, err := parseResult(&res)
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get method result: %v", err)
+	}
+	return &res, leafIndex, nil
+}
@@ -0,0 +1,44 @@
+package client
+
+import (
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+)
+
+func TestSpendGroupFee(t *testing.T) {
+	f := newTestFrontend()
+	f.App = &App{Id: 7}
+	account := crypto.GenerateAccount()
+	signer := transaction.BasicAccountTransactionSigner{Account: account}
+	note := &Note{Amount: 10_000_000}
+
+	// the default fee covers the MBR of every nullifier
+	g, err := f.newSpendGroup(note, 2, 0, account.Address, signer)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if g.fee != defaultSpendFee(2) || g.fee < 2*config.NullifierMbr+transaction.MinTxnFee {
+		t.Fatalf("unexpected default fee %d", g.fee)
+	}
+
+	// a fee below the nullifiers MBR and the transaction fee is rejected
+	for _, fee := range []uint64{config.NullifierMbr, 2 * config.NullifierMbr} {
+		if _, err := f.newSpendGroup(note, 2, fee, account.Address, signer); err == nil {
+			t.Fatalf("expected fee %d to be rejected", fee)
+		}
+	}
+	if _, err := f.newSpendGroup(note, 1, config.NullifierMbr+transaction.MinTxnFee,
+		account.Address, signer); err != nil {
+		t.Fatal(err)
+	}
+
+	// asset fees are paid to the fee recipient, which pays the MBR in Algo
+	if _, err := f.newSpendGroup(&Note{Amount: 100, AssetId: 9}, 1, 10, account.Address,
+		signer); err != nil {
+		t.Fatal(err)
+	}
+}
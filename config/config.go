@@ -15,11 +15,32 @@ const (
 
 	DepositMinimumAmount = 1_000_000 // microalgo, or 1 algo
 
-	DepositMethodName    = "deposit"
-	WithDrawalMethodName = "withdraw"
-	NoOpMethodName       = "noop"
-	CreateMethodName     = "create"
-	UpdateMethodName     = "update"
+	// Caps on the Algo value in the app, in microalgo and 0 for no cap (see APP.py):
+	// the maximum amount of a deposit, the maximum total value locked after a deposit,
+	// and the maximum withdrawn with fees in each window of WithdrawalWindowRounds
+	DepositMaximumAmount    = 0
+	TvlMaximum              = 0
+	WithdrawalWindowRounds  = 1_000
+	WithdrawalWindowMaximum = 0
+	// CapsEnabled is true if any cap is set, the app then rejects ASAs
+	CapsEnabled = DepositMaximumAmount > 0 || TvlMaximum > 0 || WithdrawalWindowMaximum > 0
+
+	// DepositAllowlist restricts deposits to the depositors added by the app admin
+	// (see client.AddDepositor), open deployments leave it false
+	DepositAllowlist = false
+
+	DepositMethodName           = "deposit"
+	WithDrawalMethodName        = "withdraw"
+	AddressWithdrawalMethodName = "withdraw_from_address"
+	NoOpMethodName              = "noop"
+	TransferMethodName          = "transfer"
+	JoinSplitMethodName         = "join_split"
+	OptInAssetMethodName        = "opt_in_asset"
+	SetAdminMethodName          = "set_admin"
+	AddDepositorMethodName      = "add_depositor"
+	RemoveDepositorMethodName   = "remove_depositor"
+	CreateMethodName            = "create"
+	UpdateMethodName            = "update"
 
 	WithdrawalMethodTxnFeeArgPos = 5
 )
@@ -34,8 +55,9 @@ const (
 	DepositOpcodeBudgetOpUp = 1100*MerkleTreeLevels + 1900
 
 	// fees needed for a withdrawal transaction group
-	// TODO: When adding the spend commitment (so now two leafs added in contract), I increased these values by even multiples
-	// We should see what the true min required values are
+	// These and the deposit values are upper bounds: setup.EstimateCosts measures the
+	// minimum values by simulation (see client.Estimate), and the next setup uses the
+	// measured opcode budgets
 	WithdrawalMinFeeMultiplier = 180
 	WithdrawalOpcodeBudgetOpUp = 3*1100*MerkleTreeLevels + 4000
 
@@ -46,6 +68,12 @@ const (
 
 	// MBR for each nullifier box storage
 	NullifierMbr = 15_300 // 2500 + 400*32
+
+	// MBR for each asset the APP address is opted in
+	AssetOptInMbr = 100_000
+
+	// MBR for each depositor box of the deposit allowlist
+	DepositorMbr = 15_700 // 2500 + 400*33
 )
 
 type HashFunc = func(...[]byte) []byte
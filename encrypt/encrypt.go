@@ -5,6 +5,7 @@ package encrypt
 
 import (
 	"crypto/rand"
+	"crypto/sha256"
 	"encoding/base64"
 	"fmt"
 	"io"
@@ -14,6 +15,8 @@ import (
 
 	bnt "github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards"
 	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"golang.org/x/crypto/chacha20poly1305"
+	"golang.org/x/crypto/hkdf"
 	"golang.org/x/crypto/nacl/secretbox"
 	"golang.org/x/crypto/scrypt"
 	"golang.org/x/term"
@@ -71,127 +74,231 @@ func Decrypt(ciphertext string) (string, error) {
 	if err != nil {
 		return "", fmt.Errorf("failed to decode input: %v", err)
 	}
-	if len(fullData) < saltSize {
-		return "", fmt.Errorf("invalid input: missing salt")
+	plaintext, err := DecryptWithPassword(fullData, passInput)
+	if err != nil {
+		return "", err
 	}
+	return string(plaintext), nil
+}
 
-	// Extract the salt and the actual encrypted data.
-	salt := fullData[:saltSize]
-	encryptedRaw := fullData[saltSize:]
-
-	// Derive the key using the extracted salt.
-	key, err := deriveKey(passInput, salt)
+// encrypt encrypts the plaintext asking the user for the password
+func Encrytp(plaintext string) (string, error) {
+	fmt.Print("Enter password: ")
+	passInput, err := term.ReadPassword(int(os.Stdin.Fd()))
 	if err != nil {
-		return "", fmt.Errorf("key derivation failed: %v", err)
+		return "", fmt.Errorf("failed to read password: %v", err)
 	}
+	fmt.Println()
 
-	plaintext, err := decryptRaw(encryptedRaw, key)
+	finalData, err := EncryptWithPassword([]byte(plaintext), passInput)
 	if err != nil {
-		return "", fmt.Errorf("decryption failed: %v", err)
+		return "", err
 	}
-	return string(plaintext), nil
+	encoded := base64.StdEncoding.EncodeToString(finalData)
+	return encoded, nil
 }
 
-// encrypt encrypts the plaintext asking the user for the password
-func Encrytp(plaintext string) (string, error) {
+// EncryptWithPassword encrypts the plaintext with a key derived from password and
+// returns salt || nonce || ciphertext
+func EncryptWithPassword(plaintext, password []byte) ([]byte, error) {
 	// Generate a random salt.
 	salt := make([]byte, saltSize)
 	if _, err := io.ReadFull(rand.Reader, salt); err != nil {
-		return "", fmt.Errorf("failed to read random data: %v", err)
+		return nil, fmt.Errorf("failed to read random data: %v", err)
 	}
 
-	fmt.Print("Enter password: ")
-	passInput, err := term.ReadPassword(int(os.Stdin.Fd()))
+	// Derive a key from the password using the random salt.
+	key, err := deriveKey(password, salt)
 	if err != nil {
-		return "", fmt.Errorf("failed to read password: %v", err)
+		return nil, fmt.Errorf("key derivation failed: %v", err)
 	}
-	fmt.Println()
 
-	// Derive a key from the password using the random salt.
-	key, err := deriveKey(passInput, salt)
+	encryptedRaw, err := encryptRaw(plaintext, key)
 	if err != nil {
-		return "", fmt.Errorf("key derivation failed: %v", err)
+		return nil, fmt.Errorf("encryption failed: %v", err)
 	}
 
-	encryptedRaw, err := encryptRaw([]byte(plaintext), key)
+	// Prepend the salt to the encrypted data.
+	return append(salt, encryptedRaw...), nil
+}
+
+// DecryptWithPassword decrypts data produced by EncryptWithPassword with password
+func DecryptWithPassword(data, password []byte) ([]byte, error) {
+	if len(data) < saltSize {
+		return nil, fmt.Errorf("invalid input: missing salt")
+	}
+
+	// Extract the salt and the actual encrypted data.
+	salt := data[:saltSize]
+	encryptedRaw := data[saltSize:]
+
+	// Derive the key using the extracted salt.
+	key, err := deriveKey(password, salt)
 	if err != nil {
-		return "", fmt.Errorf("encryption failed: %v", err)
+		return nil, fmt.Errorf("key derivation failed: %v", err)
 	}
 
-	// Prepend the salt to the encrypted data.
-	finalData := append(salt, encryptedRaw...)
-	encoded := base64.StdEncoding.EncodeToString(finalData)
-	return encoded, nil
+	plaintext, err := decryptRaw(encryptedRaw, key)
+	if err != nil {
+		return nil, fmt.Errorf("decryption failed: %v", err)
+	}
+	return plaintext, nil
 }
 
 // ECIESEncrypt encrypts data using ECIES with the given EdDSA public key and ephemeral public key
 func ECIESEncrypt(data []byte, pubkey eddsa.PublicKey, ephemeralPub eddsa.PublicKey, ephemeralPriv eddsa.PrivateKey) ([]byte, error) {
-	// Extract scalar from ephemeral private key
-	const pubSize = 32
-	const sizeFr = 32
-	privBytes := ephemeralPriv.Bytes()
-	scalarBytes := privBytes[pubSize : pubSize+sizeFr]
-	scalar := new(big.Int).SetBytes(scalarBytes)
+	key, err := ECIESSharedKey(pubkey, ephemeralPriv)
+	if err != nil {
+		return nil, err
+	}
 
-	// Compute shared point: scalar * pubkey.A
-	var sharedPoint bnt.PointAffine
-	sharedPoint.ScalarMultiplication(&pubkey.A, scalar)
+	// Encrypt the data
+	encrypted, err := encryptRaw(data, key)
+	if err != nil {
+		return nil, fmt.Errorf("encryption failed: %v", err)
+	}
+
+	return encrypted, nil
+}
+
+// ECIESDecrypt decrypts data using ECIES with the given EdDSA private key
+func ECIESDecrypt(encryptedData []byte, ephemeralPub eddsa.PublicKey, privkey eddsa.PrivateKey) ([]byte, error) {
+	key, err := ECIESSharedKey(ephemeralPub, privkey)
+	if err != nil {
+		return nil, err
+	}
 
-	// Derive encryption key from shared point
-	xBytes := sharedPoint.X.Bytes()
-	yBytes := sharedPoint.Y.Bytes()
+	// Decrypt the data
+	decrypted, err := decryptRaw(encryptedData, key)
+	if err != nil {
+		return nil, fmt.Errorf("decryption failed: %v", err)
+	}
 
-	// Use scrypt to derive a 32-byte key from the shared secret
-	sharedSecret := append(xBytes[:], yBytes[:]...)
+	return decrypted, nil
+}
+
+// ECIESSharedKey returns the symmetric key used by ECIESEncrypt and ECIESDecrypt,
+// derived with scrypt from the point shared by privkey and pubkey.
+// Use it to derive the key once when encrypting several values to the same pubkey.
+func ECIESSharedKey(pubkey eddsa.PublicKey, privkey eddsa.PrivateKey) (*[32]byte, error) {
+	sharedSecret := sharedPoint(pubkey, privkey)
 	key, err := scrypt.Key(sharedSecret, []byte("ecies"), 32768, 8, 1, 32)
 	if err != nil {
 		return nil, fmt.Errorf("key derivation failed: %v", err)
 	}
 	var keyArray [32]byte
 	copy(keyArray[:], key)
+	return &keyArray, nil
+}
 
-	// Encrypt the data
-	encrypted, err := encryptRaw(data, &keyArray)
+// eciesHKDFDomain separates the keys derived by ECIESHKDFKey from other uses of the
+// same shared point
+const eciesHKDFDomain = "Mithras/ECIES-HKDF-SHA256/v1"
+
+// ECIESEncryptHKDF encrypts data using ECIES with the given EdDSA public key, like
+// ECIESEncrypt but deriving the key with ECIESHKDFKey
+func ECIESEncryptHKDF(data []byte, pubkey eddsa.PublicKey, ephemeralPriv eddsa.PrivateKey) ([]byte, error) {
+	key, err := ECIESHKDFKey(pubkey, ephemeralPriv.PublicKey, ephemeralPriv)
+	if err != nil {
+		return nil, err
+	}
+	encrypted, err := encryptRaw(data, key)
 	if err != nil {
 		return nil, fmt.Errorf("encryption failed: %v", err)
 	}
-
 	return encrypted, nil
 }
 
-// ECIESDecrypt decrypts data using ECIES with the given EdDSA private key
-func ECIESDecrypt(encryptedData []byte, ephemeralPub eddsa.PublicKey, privkey eddsa.PrivateKey) ([]byte, error) {
-	pubKeySize := 32
+// ECIESDecryptHKDF decrypts data encrypted with ECIESEncryptHKDF
+func ECIESDecryptHKDF(encryptedData []byte, ephemeralPub eddsa.PublicKey, privkey eddsa.PrivateKey) ([]byte, error) {
+	key, err := ECIESHKDFKey(privkey.PublicKey, ephemeralPub, privkey)
+	if err != nil {
+		return nil, err
+	}
+	decrypted, err := decryptRaw(encryptedData, key)
+	if err != nil {
+		return nil, fmt.Errorf("decryption failed: %v", err)
+	}
+	return decrypted, nil
+}
+
+// ECIESHKDFKey derives the ECIES symmetric key for recipientPub and ephemeralPub with
+// HKDF-SHA256. The shared point is already high entropy, so there is no need for the
+// memory-hard scrypt of ECIESSharedKey and trial decryptions stay cheap.
+// The salt binds the key to the domain and to both public keys.
+// privkey is either the ephemeral private key (to encrypt) or the recipient one
+// (to decrypt).
+func ECIESHKDFKey(recipientPub, ephemeralPub eddsa.PublicKey, privkey eddsa.PrivateKey) (
+	*[32]byte, error) {
 
+	var other eddsa.PublicKey
+	switch {
+	case privkey.PublicKey.Equal(&ephemeralPub):
+		other = recipientPub
+	case privkey.PublicKey.Equal(&recipientPub):
+		other = ephemeralPub
+	default:
+		return nil, fmt.Errorf("private key matches neither public key")
+	}
+	sharedSecret := sharedPoint(other, privkey)
+
+	salt := []byte(eciesHKDFDomain)
+	salt = append(salt, ephemeralPub.Bytes()...)
+	salt = append(salt, recipientPub.Bytes()...)
+
+	var key [32]byte
+	kdf := hkdf.New(sha256.New, sharedSecret, salt, []byte("ecies key"))
+	if _, err := io.ReadFull(kdf, key[:]); err != nil {
+		return nil, fmt.Errorf("key derivation failed: %v", err)
+	}
+	return &key, nil
+}
+
+// sharedPoint returns the point privkey.scalar * pubkey.A serialized as X || Y
+func sharedPoint(pubkey eddsa.PublicKey, privkey eddsa.PrivateKey) []byte {
 	// Extract scalar from private key
+	const pubSize = 32
 	const sizeFr = 32
 	privBytes := privkey.Bytes()
-	scalarBytes := privBytes[pubKeySize : pubKeySize+sizeFr]
+	scalarBytes := privBytes[pubSize : pubSize+sizeFr]
 	scalar := new(big.Int).SetBytes(scalarBytes)
 
-	// Compute shared point: scalar * ephemeralPub.A
-	var sharedPoint bnt.PointAffine
-	sharedPoint.ScalarMultiplication(&ephemeralPub.A, scalar)
+	// Compute shared point: scalar * pubkey.A
+	var point bnt.PointAffine
+	point.ScalarMultiplication(&pubkey.A, scalar)
 
-	// Derive decryption key from shared point
-	xBytes := sharedPoint.X.Bytes()
-	yBytes := sharedPoint.Y.Bytes()
+	xBytes := point.X.Bytes()
+	yBytes := point.Y.Bytes()
+	return append(xBytes[:], yBytes[:]...)
+}
 
-	// Use scrypt to derive the same 32-byte key
-	sharedSecret := append(xBytes[:], yBytes[:]...)
-	key, err := scrypt.Key(sharedSecret, []byte("ecies"), 32768, 8, 1, 32)
+// SealWithKey encrypts data with XChaCha20-Poly1305 under key, authenticating also
+// additionalData. It returns nonce || ciphertext.
+func SealWithKey(key *[32]byte, data, additionalData []byte) ([]byte, error) {
+	aead, err := chacha20poly1305.NewX(key[:])
 	if err != nil {
-		return nil, fmt.Errorf("key derivation failed: %v", err)
+		return nil, err
 	}
-	var keyArray [32]byte
-	copy(keyArray[:], key)
-
-	// Decrypt the data
-	decrypted, err := decryptRaw(encryptedData, &keyArray)
+	nonce := make([]byte, aead.NonceSize(), aead.NonceSize()+len(data)+aead.Overhead())
+	if _, err := io.ReadFull(rand.Reader, nonce); err != nil {
+		return nil, err
+	}
+	return aead.Seal(nonce, nonce, data, additionalData), nil
+}
 
+// OpenWithKey decrypts the output of SealWithKey
+func OpenWithKey(key *[32]byte, sealed, additionalData []byte) ([]byte, error) {
+	aead, err := chacha20poly1305.NewX(key[:])
 	if err != nil {
-		return nil, fmt.Errorf("decryption failed: %v", err)
+		return nil, err
+	}
+	if len(sealed) < aead.NonceSize()+aead.Overhead() {
+		return nil, fmt.Errorf("encrypted data too short")
+	}
+	nonce, ciphertext := sealed[:aead.NonceSize()], sealed[aead.NonceSize():]
+	decrypted, err := aead.Open(nil, nonce, ciphertext, additionalData)
+	if err != nil {
+		return nil, fmt.Errorf("decryption error: invalid key or corrupt data")
 	}
-
 	return decrypted, nil
 }
@@ -0,0 +1,151 @@
+// Package keys derives the Mithras keys of a wallet from a single seed, backed up as a
+// mnemonic: the eddsa spending keys, the view keys and the seed of the note secrets.
+// Keys are derived along purpose/account/index paths, so that a wallet can hold
+// several accounts each with several receiving keys, and a lost device can be
+// restored from the mnemonic and its notes rediscovered by scanning.
+package keys
+
+import (
+	"bytes"
+	"context"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/binary"
+	"fmt"
+	"io"
+
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/mnemonic"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"golang.org/x/crypto/hkdf"
+)
+
+// SeedSize is the size of the seeds backed up as mnemonics
+const SeedSize = 32
+
+// derivationDomain is the HKDF salt of the key derivation
+const derivationDomain = "mithras-keys-v1"
+
+// The purposes of the derived keys, the first element of their path
+const (
+	SpendingKeyPurpose uint32 = iota
+	ViewKeyPurpose
+	NoteSeedPurpose
+)
+
+// Wallet derives the keys of a wallet from its seed
+type Wallet struct {
+	seed []byte
+}
+
+// NewMnemonic returns the 25 words mnemonic of a new random seed, with the
+// encoding and checksum of Algorand account mnemonics
+func NewMnemonic() (string, error) {
+	seed := make([]byte, SeedSize)
+	if _, err := rand.Read(seed); err != nil {
+		return "", fmt.Errorf("failed to generate seed: %v", err)
+	}
+	return mnemonic.FromKey(seed)
+}
+
+// FromMnemonic returns the wallet of the seed encoded by words
+func FromMnemonic(words string) (*Wallet, error) {
+	seed, err := mnemonic.ToKey(words)
+	if err != nil {
+		return nil, fmt.Errorf("invalid mnemonic: %v", err)
+	}
+	return FromSeed(seed)
+}
+
+// FromSeed returns the wallet of seed, which must be at least SeedSize bytes
+func FromSeed(seed []byte) (*Wallet, error) {
+	if len(seed) < SeedSize {
+		return nil, fmt.Errorf("seed must be at least %d bytes", SeedSize)
+	}
+	return &Wallet{seed: bytes.Clone(seed)}, nil
+}
+
+// Mnemonic returns the mnemonic of the wallet seed, only seeds of SeedSize bytes
+// have one
+func (w *Wallet) Mnemonic() (string, error) {
+	if len(w.seed) != SeedSize {
+		return "", fmt.Errorf("only %d bytes seeds have a mnemonic", SeedSize)
+	}
+	return mnemonic.FromKey(w.seed)
+}
+
+// SpendingKey returns the spending key at index of account, path
+// SpendingKeyPurpose/account/index
+func (w *Wallet) SpendingKey(account, index uint32) (*eddsa.PrivateKey, error) {
+	return w.eddsaKey(SpendingKeyPurpose, account, index)
+}
+
+// ViewKey returns the view key of account, path ViewKeyPurpose/account/0, to set as
+// the frontend ViewPubkey of the account
+func (w *Wallet) ViewKey(account uint32) (*eddsa.PrivateKey, error) {
+	return w.eddsaKey(ViewKeyPurpose, account, 0)
+}
+
+// NoteDerivation returns the derivation of the note secrets of account, path
+// NoteSeedPurpose/account/0, starting at note index
+func (w *Wallet) NoteDerivation(account uint32, index uint64) *client.NoteDerivation {
+	return &client.NoteDerivation{
+		Seed:  w.derive(NoteSeedPurpose, account, 0),
+		Index: index,
+	}
+}
+
+// Restore rediscovers in events the notes of the spending keys of account, in index
+// order, stopping after gapLimit consecutive keys without any note. It returns the
+// discovery of each key up to the last one with notes.
+func (w *Wallet) Restore(ctx context.Context, f *client.Frontend, events []*client.TreeEvent,
+	account, gapLimit uint32) ([]*client.Discovery, error) {
+
+	var discoveries []*client.Discovery
+	used := 0
+	for index, gap := uint32(0), uint32(0); gap < gapLimit; index++ {
+		key, err := w.SpendingKey(account, index)
+		if err != nil {
+			return nil, err
+		}
+		discovery, err := f.DiscoverNotes(ctx, events, *key)
+		if err != nil {
+			return nil, fmt.Errorf("failed to discover notes of key %d: %v", index, err)
+		}
+		discoveries = append(discoveries, discovery)
+		if len(discovery.Unspent)+len(discovery.Spent) == 0 {
+			gap++
+			continue
+		}
+		gap = 0
+		used = len(discoveries)
+	}
+	return discoveries[:used], nil
+}
+
+// eddsaKey returns the eddsa key of path, generated from the derived seed
+func (w *Wallet) eddsaKey(purpose, account, index uint32) (*eddsa.PrivateKey, error) {
+	key, err := eddsa.GenerateKey(bytes.NewReader(w.derive(purpose, account, index)))
+	if err != nil {
+		return nil, fmt.Errorf("failed to generate key %d/%d/%d: %v", purpose, account,
+			index, err)
+	}
+	return key, nil
+}
+
+// derive returns the 32 bytes seed of path, expanded from the wallet seed with
+// HKDF-SHA256 and the path as info
+func (w *Wallet) derive(purpose, account, index uint32) []byte {
+	info := binary.BigEndian.AppendUint32(nil, purpose)
+	info = binary.BigEndian.AppendUint32(info, account)
+	info = binary.BigEndian.AppendUint32(info, index)
+
+	derived := make([]byte, 32)
+	kdf := hkdf.New(sha256.New, w.seed, []byte(derivationDomain), info)
+	if _, err := io.ReadFull(kdf, derived); err != nil {
+		// HKDF-SHA256 can expand up to 255*32 bytes
+		panic(err)
+	}
+	return derived
+}
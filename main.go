@@ -1,15 +1,30 @@
 package main
 
 import (
+	"context"
 	"fmt"
 	"io"
 	"log"
+	"net/http"
 	"os"
 
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
 	"github.com/joe-p/Mithras-Protocol/deployed"
+	"github.com/joe-p/Mithras-Protocol/prover"
+	"github.com/joe-p/Mithras-Protocol/relayer"
 	"github.com/joe-p/Mithras-Protocol/setup"
 )
 
+const (
+	// defaultProverAddress is the address of the prover daemon, unless set with the
+	// MITHRAS_PROVER_ADDRESS environment variable
+	defaultProverAddress = "localhost:8090"
+	// defaultRelayerAddress is the address of the relayer daemon, unless set with the
+	// MITHRAS_RELAYER_ADDRESS environment variable
+	defaultRelayerAddress = "localhost:8091"
+)
+
 func main() {
 	// Ensure exactly two arguments are provided: command and networkName
 	if len(os.Args) != 3 {
@@ -20,9 +35,10 @@ func main() {
 	command := os.Args[1]
 	networkName := os.Args[2]
 
-	if command != "create" {
+	if command != "create" && command != "prover" && command != "relayer" &&
+		command != "estimate" && command != "reserves" {
 		fmt.Printf("Invalid command: %s\n", command)
-		fmt.Println("Valid commands are: create")
+		fmt.Println("Valid commands are: create, prover, relayer, estimate, reserves")
 		os.Exit(1)
 	}
 
@@ -42,19 +58,90 @@ func main() {
 		network = deployed.DevNet
 	}
 
-	logFile := initializeLog(network)
-	defer logFile.Close()
-
 	switch command {
 	case "create":
+		logFile := initializeLog(network)
+		defer logFile.Close()
 		setup.CreateApp(network)
+	case "prover":
+		serveProver(network)
+	case "relayer":
+		serveRelayer(network)
+	case "estimate":
+		setup.EstimateCosts(network)
+	case "reserves":
+		checkReserves(network)
+	}
+}
+
+// serveProver runs a private prover daemon for the app deployed on network
+func serveProver(network deployed.Network) {
+	app, err := client.ReadApp(network.DirPath())
+	if err != nil {
+		log.Fatalf("Failed to read app artefacts: %v", err)
+	}
+	address := os.Getenv("MITHRAS_PROVER_ADDRESS")
+	if address == "" {
+		address = defaultProverAddress
+	}
+	server := prover.NewServer(app, prover.Config{Private: true})
+	server.Start(context.Background())
+	log.Printf("Prover listening on %s", address)
+	log.Fatal(http.ListenAndServe(address, server))
+}
+
+// serveRelayer runs a relayer daemon for the app deployed on network, paying the
+// transaction fees with the TSS
+func serveRelayer(network deployed.Network) {
+	avm.Initialize(network)
+	f, err := client.NewFrontend(network, avm.GetAlgodClient())
+	if err != nil {
+		log.Fatalf("Failed to create frontend: %v", err)
+	}
+	address := os.Getenv("MITHRAS_RELAYER_ADDRESS")
+	if address == "" {
+		address = defaultRelayerAddress
+	}
+	server := relayer.NewServer(f, relayer.Config{})
+	log.Printf("Relayer listening on %s", address)
+	log.Fatal(http.ListenAndServe(address, server))
+}
+
+// checkReserves checks the reserves of the app deployed on network against its
+// history read from the blocks of the node, and exits with an error on discrepancies
+func checkReserves(network deployed.Network) {
+	avm.Initialize(network)
+	f, err := client.NewFrontend(network, avm.GetAlgodClient())
+	if err != nil {
+		log.Fatalf("Failed to create frontend: %v", err)
+	}
+	reserves, err := f.CheckReserves(context.Background(),
+		&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		log.Fatalf("Failed to check reserves: %v", err)
+	}
+	log.Printf("Round %d: %d nullifiers, %d nullifier boxes, %d depositors, "+
+		"%d assets opted in, min balance %d (expected %d)", reserves.Round,
+		reserves.Nullifiers, reserves.NullifierBoxes, reserves.Depositors,
+		reserves.OptedInAssets, reserves.MinBalance, reserves.ExpectedMinBalance)
+	for _, a := range reserves.Assets {
+		log.Printf("Asset %d: deposited %d, withdrawn %d, fees %d, unspent %d, "+
+			"balance %d (expected %d)", a.AssetId, a.Deposited, a.Withdrawn, a.Fees,
+			a.Unspent, a.Balance, a.Expected)
+	}
+	for _, d := range reserves.Discrepancies {
+		log.Printf("Discrepancy: %s", d)
+	}
+	if len(reserves.Discrepancies) > 0 {
+		os.Exit(1)
 	}
+	log.Println("Reserves match")
 }
 
 // helpString returns the help string for the command line interface
 func helpString() string {
 	help := "Usage: <command> <networkName>\n"
-	help += "Commands: create\n"
+	help += "Commands: create, prover, relayer, estimate, reserves\n"
 	help += "Networks: mainnet, testnet, devnet\n"
 	return help
 }
@@ -0,0 +1,172 @@
+// Package prover is a proof generation daemon for wallets which cannot prove
+// themselves, such as mobile and browser wallets. It loads the compiled circuits of a
+// deployed app once and proves the assignments posted to its HTTP API, see Server.
+package prover
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"log"
+	"net/http"
+	"time"
+
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/consensys/gnark/frontend"
+)
+
+// maxRequestSize bounds the body of prove requests, the join-split assignment with
+// its merkle paths is well under it
+const maxRequestSize = 1 << 20
+
+// Config configures a Server
+type Config struct {
+	// Workers is the number of proofs generated concurrently, 1 if 0
+	Workers int
+	// QueueSize is the number of requests waiting for a worker, beyond which requests
+	// are rejected with 503, 16 if 0
+	QueueSize int
+	// Private never logs nor returns the details of failed proofs, since the errors
+	// of unsatisfied constraints can hold values of the secret witness
+	Private bool
+}
+
+// Server proves the assignments posted to POST /prove as a client.ProveRequest and
+// answers with a client.Proof, whose proof and public inputs are the arguments of
+// utils.ProofAndPublicInputsForAtomicComposer, or with a client.ErrorResponse.
+// Requests are queued and proved by Config.Workers workers.
+type Server struct {
+	prover client.Prover
+	config Config
+	queue  chan *job
+}
+
+type job struct {
+	ctx        context.Context
+	method     string
+	assignment frontend.Circuit
+	done       chan result
+}
+
+type result struct {
+	proof *client.Proof
+	err   error
+}
+
+// NewServer returns a Server proving with the compiled circuits of app
+func NewServer(app *client.App, config Config) *Server {
+	if config.Workers <= 0 {
+		config.Workers = 1
+	}
+	if config.QueueSize <= 0 {
+		config.QueueSize = 16
+	}
+	return &Server{
+		prover: &client.LocalProver{App: app},
+		config: config,
+		queue:  make(chan *job, config.QueueSize),
+	}
+}
+
+// Start starts the workers, which stop when ctx is done
+func (s *Server) Start(ctx context.Context) {
+	for range s.config.Workers {
+		go s.work(ctx)
+	}
+}
+
+// work proves the queued jobs until ctx is done
+func (s *Server) work(ctx context.Context) {
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case j := <-s.queue:
+			// the client may have given up while the job was queued
+			if j.ctx.Err() != nil {
+				j.done <- result{err: j.ctx.Err()}
+				continue
+			}
+			proof, err := s.prover.Prove(j.ctx, j.method, j.assignment)
+			j.done <- result{proof: proof, err: err}
+		}
+	}
+}
+
+// ServeHTTP serves POST /prove
+func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if r.URL.Path != "/prove" {
+		http.NotFound(w, r)
+		return
+	}
+	if r.Method != http.MethodPost {
+		writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+		return
+	}
+	start := time.Now()
+
+	req := client.ProveRequest{}
+	body := http.MaxBytesReader(w, r.Body, maxRequestSize)
+	if err := json.NewDecoder(body).Decode(&req); err != nil {
+		s.fail(w, http.StatusBadRequest, req.Method, "invalid request", err)
+		return
+	}
+	assignment, err := client.NewAssignment(req.Method)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if err := json.Unmarshal(req.Assignment, assignment); err != nil {
+		s.fail(w, http.StatusBadRequest, req.Method, "invalid assignment", err)
+		return
+	}
+
+	j := &job{
+		ctx:        r.Context(),
+		method:     req.Method,
+		assignment: assignment,
+		done:       make(chan result, 1),
+	}
+	select {
+	case s.queue <- j:
+	default:
+		log.Printf("prover: %s request rejected, queue full", req.Method)
+		writeError(w, http.StatusServiceUnavailable, "prover queue full, retry later")
+		return
+	}
+
+	var res result
+	select {
+	case res = <-j.done:
+	case <-r.Context().Done():
+		return
+	}
+	if res.err != nil {
+		s.fail(w, http.StatusUnprocessableEntity, req.Method, "proof generation failed",
+			res.err)
+		return
+	}
+	log.Printf("prover: %s proved in %s", req.Method, time.Since(start).Round(time.Millisecond))
+	writeJSON(w, http.StatusOK, res.proof)
+}
+
+// fail logs and answers a failed request, with the details of err unless the server
+// is private
+func (s *Server) fail(w http.ResponseWriter, status int, method, message string, err error) {
+	if !s.config.Private {
+		message = fmt.Sprintf("%s: %v", message, err)
+	}
+	log.Printf("prover: %s request failed: %s", method, message)
+	writeError(w, status, message)
+}
+
+func writeError(w http.ResponseWriter, status int, message string) {
+	writeJSON(w, status, &client.ErrorResponse{Error: message})
+}
+
+func writeJSON(w http.ResponseWriter, status int, v interface{}) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	json.NewEncoder(w).Encode(v)
+}
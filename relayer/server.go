@@ -0,0 +1,182 @@
+// Package relayer is a daemon sending withdrawals on behalf of users without Algo to
+// pay the transaction fees, e.g. withdrawing to a new address. Users post a proved
+// withdrawal (see client.PrepareWithdrawal) and the relayer pays the fees of the
+// group from the withdrawal fee, see Server.
+package relayer
+
+import (
+	"bytes"
+	"context"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"log"
+	"net/http"
+	"sync"
+
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// maxRequestSize bounds the body of relay requests, a withdrawal proof with its
+// public inputs and notes is well under it
+const maxRequestSize = 64 << 10
+
+// Config configures a Server
+type Config struct {
+	// FeeRecipient receives the withdrawal fees and pays the transaction fees of the
+	// groups, with the padding transactions signed by FeeSigner. The TSS if not set.
+	FeeRecipient types.Address
+	FeeSigner    transaction.TransactionSigner
+	// MinFee is the minimum fee of the relayed withdrawals, at least
+	// client.DefaultWithdrawalFee(0) which only covers the transaction fees and the
+	// nullifier MBR
+	MinFee uint64
+	// MaxRootAge is the number of roots added after the root of a proof beyond which
+	// the proof is rejected as stale, so that its root is still accepted by the app
+	// when the group is confirmed. config.RootsCount/2 if 0.
+	MaxRootAge int
+}
+
+// Server sends the withdrawals posted to POST /relay as a client.RelayedWithdrawal
+// and answers with a client.RelayResult, or with a client.ErrorResponse.
+// Before signing, it rejects the withdrawals whose fee is below Config.MinFee, whose
+// root is stale, whose nullifier is spent or already being relayed, and the groups
+// failing simulation, so that no fee is spent on groups that cannot succeed.
+type Server struct {
+	f      *client.Frontend
+	config Config
+
+	mu sync.Mutex
+	// pending holds the hex nullifiers of the withdrawals being relayed
+	pending map[string]bool
+}
+
+// NewServer returns a Server sending the withdrawals with the app and algod client
+// of f
+func NewServer(f *client.Frontend, cfg Config) *Server {
+	if cfg.FeeRecipient.IsZero() || cfg.FeeSigner == nil {
+		cfg.FeeRecipient = f.App.TSS.Address
+		cfg.FeeSigner = transaction.LogicSigAccountTransactionSigner{
+			LogicSigAccount: f.App.TSS.Account,
+		}
+	}
+	cfg.MinFee = max(cfg.MinFee, client.DefaultWithdrawalFee(0))
+	if cfg.MaxRootAge <= 0 || cfg.MaxRootAge > config.RootsCount {
+		cfg.MaxRootAge = config.RootsCount / 2
+	}
+	return &Server{
+		f:       f,
+		config:  cfg,
+		pending: map[string]bool{},
+	}
+}
+
+// ServeHTTP serves POST /relay
+func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if r.URL.Path != "/relay" {
+		http.NotFound(w, r)
+		return
+	}
+	if r.Method != http.MethodPost {
+		writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+		return
+	}
+
+	req := &client.RelayedWithdrawal{}
+	body := http.MaxBytesReader(w, r.Body, maxRequestSize)
+	if err := json.NewDecoder(body).Decode(req); err != nil {
+		writeError(w, http.StatusBadRequest, fmt.Sprintf("invalid request: %v", err))
+		return
+	}
+	inputs, err := req.Inputs()
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if inputs.AssetId != 0 {
+		writeError(w, http.StatusUnprocessableEntity, "only Algo withdrawals are relayed")
+		return
+	}
+	if inputs.Fee < s.config.MinFee {
+		writeError(w, http.StatusUnprocessableEntity, fmt.Sprintf(
+			"fee %d below the relayer fee %d", inputs.Fee, s.config.MinFee))
+		return
+	}
+
+	nullifier := hex.EncodeToString(inputs.Nullifier)
+	if !s.claim(nullifier) {
+		writeError(w, http.StatusConflict, "withdrawal already being relayed")
+		return
+	}
+	defer s.release(nullifier)
+
+	if status, err := s.check(r.Context(), inputs); err != nil {
+		writeError(w, status, err.Error())
+		return
+	}
+
+	res, err := s.f.SendRelayedWithdrawal(req, s.config.FeeRecipient, s.config.FeeSigner)
+	if err != nil {
+		log.Printf("relayer: withdrawal %s failed: %v", nullifier, err)
+		writeError(w, http.StatusUnprocessableEntity, err.Error())
+		return
+	}
+	log.Printf("relayer: withdrawal %s relayed in %s", nullifier, res.TxnIds[0])
+	writeJSON(w, http.StatusOK, res)
+}
+
+// check rejects the withdrawal of inputs if its nullifier is spent or its root is
+// stale, and returns the status of the rejection
+func (s *Server) check(ctx context.Context, inputs *client.WithdrawalInputs) (int, error) {
+	spent, err := s.f.NullifierSpent(ctx, inputs.Nullifier)
+	if err != nil {
+		return http.StatusBadGateway, err
+	}
+	if spent {
+		return http.StatusConflict, fmt.Errorf("nullifier already spent")
+	}
+
+	roots, err := s.f.RecentRoots(ctx)
+	if err != nil {
+		return http.StatusBadGateway, err
+	}
+	for _, root := range roots[:s.config.MaxRootAge] {
+		if bytes.Equal(root, inputs.Root) {
+			return http.StatusOK, nil
+		}
+	}
+	return http.StatusUnprocessableEntity, fmt.Errorf(
+		"stale or unknown root, prove again with the current root")
+}
+
+// claim marks nullifier as being relayed, it returns false if it already is
+func (s *Server) claim(nullifier string) bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.pending[nullifier] {
+		return false
+	}
+	s.pending[nullifier] = true
+	return true
+}
+
+// release unmarks nullifier once relayed, or failed
+func (s *Server) release(nullifier string) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	delete(s.pending, nullifier)
+}
+
+func writeError(w http.ResponseWriter, status int, message string) {
+	writeJSON(w, status, &client.ErrorResponse{Error: message})
+}
+
+func writeJSON(w http.ResponseWriter, status int, v interface{}) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	json.NewEncoder(w).Encode(v)
+}
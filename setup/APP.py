@@ -1,7 +1,7 @@
 import typing
 
 import algopy as py
-from algopy import Account, Bytes, Global, Txn, UInt64, itxn, op, subroutine, urange
+from algopy import Account, Asset, Bytes, Global, Txn, UInt64, itxn, op, subroutine, urange
 from algopy.arc4 import Address, Bool, Byte, DynamicArray, StaticArray, abimethod
 
 Bytes32: typing.TypeAlias = StaticArray[Byte, typing.Literal[32]]
@@ -10,6 +10,24 @@ CURVE_MOD = 52435875175126190479447740508185965837690552500527637822603658699938
 
 DEPOSIT_MINIMUM_AMOUNT = 1_000_000 # 1 Algo
 
+# Caps on the Algo value in the app (microalgo), 0 for no cap: the maximum amount of a
+# deposit, the maximum total value locked after a deposit, and the maximum value spent
+# from notes (withdrawals and fees) in each window of WITHDRAWAL_WINDOW_ROUNDS rounds
+DEPOSIT_MAXIMUM_AMOUNT = 0
+TVL_MAXIMUM = 0
+WITHDRAWAL_WINDOW_ROUNDS = 1_000
+WITHDRAWAL_WINDOW_MAXIMUM = 0
+
+# app MBR increase for each asset the app is opted in (microalgo)
+ASSET_OPT_IN_MBR = 100_000
+
+# If True, only the depositors in the allowlist managed by the admin can deposit
+DEPOSIT_ALLOWLIST = False
+
+# app MBR increase for each depositor in the allowlist (microalgo)
+# 2500 + 400 * 33 = 15_700
+DEPOSITOR_MBR = 15_700
+
 # Depth of the Merkle tree to store the commitments, not counting the root.
 # The leaves are at depth 0 and there are 2**tree_depth leaves.
 # The tree is inizialized with the hash of 0 for all leaves
@@ -28,21 +46,56 @@ WITHDRAWAL_OPCODE_BUDGET_OPUP = 109_600
 # 2500 + 400 * 32 = 15_300
 NULLIFIER_MBR = 15_300
 
+# Number of notes spent and created by a join-split
+JOIN_SPLIT_INPUTS = 2
+JOIN_SPLIT_OUTPUTS = 2
+
 # The variable in  global storage are:
 # initialized           -> initially false, will be set to true after initialization
 # TSS                   -> treasury smart signature address for reference
 # inserted_leaves_count -> number of leaves inserted in the tree
 # root                  -> current root hash
 # next_root_index       -> index of the next root to add, between 0,roots_count
+# admin                 -> account managing the depositor allowlist, the creator at first
+# window_start          -> first round of the current withdrawal window
+# window_outflow        -> Algo spent from notes in the current withdrawal window
 
 # In box storage we have (key -> value):
 # b'roots'              -> 32*roots_count bytes
 # b'subtree'            -> 32*(tree_depth) bytes (see below)
 # <32_byte_nullifier>   -> if it exists, nullifier was spent
+#                          (hash(nullifier_key, leaf_index, k), see circuits/nullifier.go)
+# b'd' + <32_byte_addr> -> if it exists, the address is in the depositor allowlist
 
 # In 'subtree' we store a compact representation of the merkle tree: path from
 # last inserted leaf to root (excluded), enough to recompute the root on insertions
 
+# Notes can hold Algo (asset id 0) or an ASA. Before the first deposit of an ASA, anyone
+# can opt the app in the asset with `opt_in_asset`, paying the asset MBR; the app never
+# opts out. ASA deposits have no minimum amount since the asset decimals are unknown.
+# ASA withdrawals send the asset to the recipient and the fee, in the asset, to the
+# fee recipient, which must both be opted in; the nullifier box MBR is paid in Algo
+# by the fee recipient with a payment to the app following the withdraw call.
+
+# With DEPOSIT_ALLOWLIST, only the addresses in the depositor allowlist can deposit, e.g.
+# the registered organisations of a cash-assistance programme. The admin adds them
+# with `add_depositor`, paying their box MBR, and removes them with `remove_depositor`,
+# which refunds it; `set_admin` hands the role over. Open deployments leave
+# DEPOSIT_ALLOWLIST False and ignore the allowlist.
+
+# Transfers spend a note into a note for another user and a change note without any
+# withdrawal: the only value leaving the app is the fee, paid as for withdrawals.
+# Join-splits are transfers spending JOIN_SPLIT_INPUTS notes, with a nullifier box each,
+# into JOIN_SPLIT_OUTPUTS notes.
+
+# The caps bound what a bug or a compromised circuit can take from the app: deposits are
+# checked against DEPOSIT_MAXIMUM_AMOUNT and TVL_MAXIMUM, and the withdrawals and fees of
+# the spending methods against WITHDRAWAL_WINDOW_MAXIMUM per window of rounds. The total
+# value locked is the app balance above its MBR. The caps are in microalgo and apply to
+# Algo notes only, as the deposit minimum, since the asset decimals are unknown: with any
+# cap set, the app cannot opt in assets and ASA notes can neither be deposited nor spent,
+# so that no ASA pool escapes the caps.
+
 # Note that the app needs to be prefunded with MBR for roots and subtree boxes (e.g.,
 # with 32 tree depth and 50 roots, 2500 + 400 * (5 + 32*50) = 644,500 microalgo for roots
 # and 2500 + 400 * (7 + 32*32) = 414_900 microalgo for the subtree)
@@ -63,6 +116,9 @@ class APP(py.ARC4Contract, avm_version=11):
         self.inserted_leaves_count = UInt64(0)
         self.root = Bytes32.from_bytes(b'')
         self.next_root_index = UInt64(0)
+        self.admin = Txn.sender
+        self.window_start = UInt64(0)
+        self.window_outflow = UInt64(0)
 
     @abimethod
     def init(self, tss: Account) -> None:
@@ -77,6 +133,58 @@ class APP(py.ARC4Contract, avm_version=11):
         self.TSS = tss
         self.initialized = True
 
+    @abimethod
+    def opt_in_asset(self, asset: Asset) -> None:
+        """Opt the application in `asset`, so that it can receive deposits of it.
+           This transaction must be preceded by a payment of the asset MBR to the
+           application and cover the inner transaction fee"""
+        mbr_txn = py.gtxn.PaymentTransaction(op.Txn.group_index - 1)
+        assert mbr_txn.receiver == Global.current_application_address, "Wrong receiver"
+        assert mbr_txn.amount == ASSET_OPT_IN_MBR, "Incorrect MBR amount"
+        assert not Global.current_application_address.is_opted_in(asset), "Already opted in"
+        assert_algo_with_caps(asset.id)
+
+        itxn.AssetTransfer(
+            xfer_asset=asset,
+            asset_receiver=Global.current_application_address,
+            asset_amount=0,
+            fee=0
+        ).submit()
+
+    @abimethod
+    def set_admin(self, admin: Address) -> None:
+        """Set the admin managing the depositor allowlist (admin only, with
+           DEPOSIT_ALLOWLIST)"""
+        assert DEPOSIT_ALLOWLIST, "Deposit allowlist disabled"
+        assert Txn.sender == self.admin, "Sender is not the admin"
+        self.admin = admin.native
+
+    @abimethod
+    def add_depositor(self, depositor: Address) -> None:
+        """Add `depositor` to the depositor allowlist (admin only, with DEPOSIT_ALLOWLIST).
+           This transaction must be preceded by a payment of DEPOSITOR_MBR to the
+           application"""
+        assert DEPOSIT_ALLOWLIST, "Deposit allowlist disabled"
+        assert Txn.sender == self.admin, "Sender is not the admin"
+        mbr_txn = py.gtxn.PaymentTransaction(op.Txn.group_index - 1)
+        assert mbr_txn.receiver == Global.current_application_address, "Wrong receiver"
+        assert mbr_txn.amount == DEPOSITOR_MBR, "Incorrect MBR amount"
+        assert op.Box.create(depositor_key(depositor), 0), "Depositor already allowed"
+
+    @abimethod
+    def remove_depositor(self, depositor: Address) -> None:
+        """Remove `depositor` from the depositor allowlist (admin only, with
+           DEPOSIT_ALLOWLIST), refunding DEPOSITOR_MBR to the admin. This transaction
+           must cover the inner transaction fee"""
+        assert DEPOSIT_ALLOWLIST, "Deposit allowlist disabled"
+        assert Txn.sender == self.admin, "Sender is not the admin"
+        assert op.Box.delete(depositor_key(depositor)), "Depositor not allowed"
+        itxn.Payment(
+            receiver=Txn.sender,
+            amount=DEPOSITOR_MBR,
+            fee=0
+        ).submit()
+
     @abimethod
     def noop(self, counter: UInt64) -> None:
         """No operation, use to make dummy app calls to increase opcode budget"""
@@ -88,32 +196,61 @@ class APP(py.ARC4Contract, avm_version=11):
         proof: DynamicArray[Bytes32],
         public_inputs: DynamicArray[Bytes32],
             # amount
+            # asset_id (0 for Algo)
             # commitment
         sender: Address,
     ) -> tuple[UInt64, Bytes32]: # return commitment leaf index and tree root
         """Deposit funds.
            This transaction must be signed by the deposit verifier which verifies the
-           zk-proof and public inputs, and be followed by a payment transaction with sender
-           matching the `sender` argument
+           zk-proof and public inputs, and be followed by a payment transaction (or an
+           asset transfer transaction for ASAs) with sender matching the `sender` argument.
+           With DEPOSIT_ALLOWLIST, `sender` must be in the depositor allowlist
         """
         py.ensure_budget(DEPOSIT_OPCODE_BUDGET_OPUP, fee_source=py.OpUpFeeSource.GroupCredit)
 
-        # Extract the amount and commitment from the public inputs
+        # Extract the amount, asset id and commitment from the public inputs
         amount = value_from_Bytes32(public_inputs[0].copy())
-        commitment = public_inputs[1].copy()
+        asset_id = value_from_Bytes32(public_inputs[1].copy())
+        commitment = public_inputs[2].copy()
 
         # Verify the proof was validated by the deposit verifier logicsig
         # by checking the transaction is signed by the deposit verifier
         assert Txn.sender == py.TemplateVar[Account]("DEPOSIT_VERIFIER_ADDRESS"), (
             "Transaction is not signed by the deposit verifier")
 
-        # Check next transaction in the group is a payment of `amount` to the application,
-        # the amount is at least the minimum deposit, and the sender is the expected one
-        pay_txn = py.gtxn.PaymentTransaction(op.Txn.group_index + 1)
-        assert pay_txn.receiver == Global.current_application_address, "Wrong receiver"
-        assert pay_txn.amount == amount, "Incorrect amount received"
-        assert pay_txn.amount >= DEPOSIT_MINIMUM_AMOUNT, "Amount is less than minimum deposit"
-        assert pay_txn.sender == sender, "Sender is not the expected one"
+        if asset_id == 0:
+            # Check next transaction in the group is a payment of `amount` to the
+            # application, the amount is at least the minimum deposit, and the sender is
+            # the expected one
+            pay_txn = py.gtxn.PaymentTransaction(op.Txn.group_index + 1)
+            assert pay_txn.receiver == Global.current_application_address, "Wrong receiver"
+            assert pay_txn.amount == amount, "Incorrect amount received"
+            assert pay_txn.amount >= DEPOSIT_MINIMUM_AMOUNT, (
+                "Amount is less than minimum deposit")
+            assert pay_txn.sender == sender, "Sender is not the expected one"
+
+            # Check the deposit and the total value locked after it are within the caps
+            if DEPOSIT_MAXIMUM_AMOUNT > 0:
+                assert amount <= DEPOSIT_MAXIMUM_AMOUNT, "Amount is more than maximum deposit"
+            if TVL_MAXIMUM > 0:
+                app = Global.current_application_address
+                assert app.balance - app.min_balance + amount <= TVL_MAXIMUM, (
+                    "Total value locked cap exceeded")
+        else:
+            # Check next transaction in the group is a transfer of `amount` of the asset
+            # to the application and the sender is the expected one
+            assert_algo_with_caps(asset_id)
+            axfer_txn = py.gtxn.AssetTransferTransaction(op.Txn.group_index + 1)
+            assert axfer_txn.xfer_asset.id == asset_id, "Wrong asset"
+            assert axfer_txn.asset_receiver == Global.current_application_address, (
+                "Wrong receiver")
+            assert axfer_txn.asset_amount == amount, "Incorrect amount received"
+            assert axfer_txn.asset_amount > 0, "Amount is zero"
+            assert axfer_txn.sender == sender, "Sender is not the expected one"
+
+        if DEPOSIT_ALLOWLIST:
+            _size, allowed = op.Box.length(depositor_key(sender))
+            assert allowed, "Depositor not allowed"
 
         # Fail if the tree is full, no more deposit accepted
         assert self.tree_not_full(), "Tree is full"
@@ -131,9 +268,11 @@ class APP(py.ARC4Contract, avm_version=11):
             # recipient_mod (address mod curve_mod)
             # withdrawal
             # fee
-            # commitment
+            # asset_id (0 for Algo)
             # nullifier
             # root
+            # unspent_commitment
+            # spend_commitment
         recipient: Account,
         fee_recipient: Account,
         no_change: Bool,
@@ -150,66 +289,227 @@ class APP(py.ARC4Contract, avm_version=11):
 
            APP will send `fee - NULLIFIER_MBR` algo to the `fee_recipient` (e.g., the TSS)
            so that it can pay the transaction fees.
+
+           For ASA notes, the withdrawal and the fee are sent in the asset, and this
+           transaction must be followed by a payment of NULLIFIER_MBR algo from the
+           `fee_recipient` to the application.
+        """
+        py.ensure_budget(WITHDRAWAL_OPCODE_BUDGET_OPUP, fee_source=py.OpUpFeeSource.GroupCredit)
+
+        # Verify the proof was validated by the withdrawal verifier logicsig
+        # by checking the transaction is signed by the withdrawal verifier
+        assert Txn.sender == py.TemplateVar[py.Account]("WITHDRAWAL_VERIFIER_ADDRESS"), (
+            "Transaction is not signed by the withdrawal verifier")
+
+        return self.spend(public_inputs, recipient, fee_recipient, no_change)
+
+    @abimethod
+    def withdraw_from_address(
+        self,
+        proof: DynamicArray[Bytes32],
+        public_inputs: DynamicArray[Bytes32],
+            # recipient_mod (address mod curve_mod)
+            # withdrawal
+            # fee
+            # asset_id (0 for Algo)
+            # nullifier
+            # root
+            # unspent_commitment
+            # spend_commitment
+            # owner_mod (address mod curve_mod)
+        recipient: Account,
+        fee_recipient: Account,
+        no_change: Bool,
+        owner: Account,
+    ) -> tuple[UInt64, Bytes32]: # return commitment leaf index and tree root
+        """Withdraw funds from a note owned by the Algorand address `owner`.
+
+           This transaction must be signed by the address withdrawal verifier which
+           verifies the zk-proof and public inputs, and the group must be authorized
+           by `owner`: one of its transactions must be sent by `owner`, so that msig,
+           lsig and rekeyed accounts are supported. App accounts cannot authorize a
+           withdrawal, since they only send inner transactions.
+
+           The other arguments are as in `withdraw`.
         """
         py.ensure_budget(WITHDRAWAL_OPCODE_BUDGET_OPUP, fee_source=py.OpUpFeeSource.GroupCredit)
 
+        # Verify the proof was validated by the address withdrawal verifier logicsig
+        # by checking the transaction is signed by the address withdrawal verifier
+        assert Txn.sender == py.TemplateVar[py.Account]("ADDRESS_WITHDRAWAL_VERIFIER_ADDRESS"), (
+            "Transaction is not signed by the address withdrawal verifier")
+
+        # Check mod of owner address matches owner_mod and the owner authorized the group
+        assert public_inputs[8] == address_mod(owner), "Owner address mod does not match"
+        assert owner != Global.current_application_address, "Invalid owner"
+        assert authorized_by(owner), "Group not authorized by the owner"
+
+        return self.spend(public_inputs, recipient, fee_recipient, no_change)
+
+    @abimethod
+    def transfer(
+        self,
+        proof: DynamicArray[Bytes32],
+        public_inputs: DynamicArray[Bytes32],
+            # fee
+            # asset_id (0 for Algo)
+            # nullifier
+            # root
+            # unspent_commitment
+            # spend_commitment
+        fee_recipient: Account,
+    ) -> tuple[UInt64, Bytes32]: # return commitment leaf index and tree root
+        """Transfer funds to the owner of `spend_commitment`, inside the protocol.
+
+           This transaction must be signed by the transfer verifier which verifies the
+           zk-proof and public inputs.
+
+           Nothing is withdrawn, the fee is paid to `fee_recipient` as in `withdraw`,
+           including the nullifier MBR payment following this transaction for ASA notes.
+           Both commitments are added to the tree, so the tree must have room for them.
+        """
+        py.ensure_budget(WITHDRAWAL_OPCODE_BUDGET_OPUP, fee_source=py.OpUpFeeSource.GroupCredit)
+
+        # Verify the proof was validated by the transfer verifier logicsig
+        # by checking the transaction is signed by the transfer verifier
+        assert Txn.sender == py.TemplateVar[py.Account]("TRANSFER_VERIFIER_ADDRESS"), (
+            "Transaction is not signed by the transfer verifier")
+
+        # Extract the public input
+        fee = value_from_Bytes32(public_inputs[0].copy())
+        asset_id = value_from_Bytes32(public_inputs[1].copy())
+        nullifier = public_inputs[2].copy()
+        root = public_inputs[3].copy()
+        unspent_commitment = public_inputs[4].copy()
+        spend_commitment = public_inputs[5].copy()
+
+        nullify(nullifier, root)
+        self.add_outflow(asset_id, fee)
+        pay_fee(asset_id, fee, fee_recipient, UInt64(NULLIFIER_MBR))
+        self.add_outputs(unspent_commitment, spend_commitment)
+
+        return (self.inserted_leaves_count - 2, self.root.copy())
+
+    @abimethod
+    def join_split(
+        self,
+        proof: DynamicArray[Bytes32],
+        public_inputs: DynamicArray[Bytes32],
+            # fee
+            # asset_id (0 for Algo)
+            # root
+            # nullifier, JOIN_SPLIT_INPUTS times
+            # commitment, JOIN_SPLIT_OUTPUTS times
+        fee_recipient: Account,
+    ) -> tuple[UInt64, Bytes32]: # return first commitment leaf index and tree root
+        """Spend JOIN_SPLIT_INPUTS notes into JOIN_SPLIT_OUTPUTS notes, inside the
+           protocol.
+
+           This transaction must be signed by the join-split verifier which verifies the
+           zk-proof and public inputs.
+
+           The fee is paid as in `transfer`, with a nullifier MBR for each input: for Algo
+           notes the fee must cover them, for ASA notes the payment following this
+           transaction must be of JOIN_SPLIT_INPUTS * NULLIFIER_MBR algo.
+        """
+        py.ensure_budget(WITHDRAWAL_OPCODE_BUDGET_OPUP, fee_source=py.OpUpFeeSource.GroupCredit)
+
+        # Verify the proof was validated by the join-split verifier logicsig
+        # by checking the transaction is signed by the join-split verifier
+        assert Txn.sender == py.TemplateVar[py.Account]("JOIN_SPLIT_VERIFIER_ADDRESS"), (
+            "Transaction is not signed by the join-split verifier")
+
+        # Extract the public input
+        fee = value_from_Bytes32(public_inputs[0].copy())
+        asset_id = value_from_Bytes32(public_inputs[1].copy())
+        root = public_inputs[2].copy()
+
+        # Add each nullifier to the spent nullifiers. Spending the same note twice
+        # gives equal nullifiers, so the second box creation fails
+        for i in urange(JOIN_SPLIT_INPUTS):
+            nullify(public_inputs[3 + i].copy(), root)
+
+        self.add_outflow(asset_id, fee)
+        pay_fee(asset_id, fee, fee_recipient, UInt64(JOIN_SPLIT_INPUTS * NULLIFIER_MBR))
+
+        for i in urange(JOIN_SPLIT_OUTPUTS):
+            assert self.tree_not_full(), "Tree is full"
+            self.update_tree_with(public_inputs[3 + JOIN_SPLIT_INPUTS + i].copy())
+
+        return (self.inserted_leaves_count - JOIN_SPLIT_OUTPUTS, self.root.copy())
+
+    @subroutine
+    def spend(
+        self,
+        public_inputs: DynamicArray[Bytes32],
+        recipient: Account,
+        fee_recipient: Account,
+        no_change: Bool,
+    ) -> tuple[UInt64, Bytes32]:
+        """Spend a note with verified public inputs as described in `withdraw`"""
         # Extract the public input
         recipient_mod = public_inputs[0].copy()
         withdrawal_bytes = public_inputs[1].copy()
         fee_bytes = public_inputs[2].copy()
-        nullifier = public_inputs[3].copy()
-        root = public_inputs[4].copy()
-        unspent_commitment = public_inputs[5].copy()
-        spend_commitment = public_inputs[6].copy()
+        asset_id = value_from_Bytes32(public_inputs[3].copy())
+        nullifier = public_inputs[4].copy()
+        root = public_inputs[5].copy()
+        unspent_commitment = public_inputs[6].copy()
+        spend_commitment = public_inputs[7].copy()
 
         # Check mod of recipient address matches recipient_mod
-        assert recipient_mod == Bytes32.from_bytes(
-            py.op.bzero(32)
-            |
-            (py.BigUInt.from_bytes(recipient.bytes) % CURVE_MOD).bytes
-        ), "Recipient address mod does not match"
+        assert recipient_mod == address_mod(recipient), "Recipient address mod does not match"
 
-        # Verify the proof was validated by the withdrawal verifier logicsig
-        # by checking the transaction is signed by the withdrawal verifier
-        assert Txn.sender == py.TemplateVar[py.Account]("WITHDRAWAL_VERIFIER_ADDRESS"), (
-            "Transaction is not signed by the withdrawal verifier")
-
-        # Add the nullifier to the spent nullifiers, or fail if it already exists
-        assert op.Box.create(nullifier.bytes, 0), "Nullifier already exists"
-
-        # Check the root is valid
-        assert valid_root(root), "Invalid root"
+        nullify(nullifier, root)
 
-        # Check the fee is not less than the MBR for the nullifier box
         fee = value_from_Bytes32(fee_bytes)
-        assert fee >= NULLIFIER_MBR, "Fee too low"
-
         withdrawal = value_from_Bytes32(withdrawal_bytes)
+        self.add_outflow(asset_id, withdrawal + fee)
 
         # Send the withdrawal to the recipient
-        itxn.Payment(
-            receiver=recipient,
-            amount=withdrawal,
-            fee=0
-        ).submit()
-
-        # Transfer any extra fee to fee_recipient (e.g., the TSS)
-        if fee > NULLIFIER_MBR:
+        if asset_id == 0:
             itxn.Payment(
-                receiver=fee_recipient,
-                amount=fee - NULLIFIER_MBR,
+                receiver=recipient,
+                amount=withdrawal,
+                fee=0
+            ).submit()
+        elif withdrawal > 0:
+            itxn.AssetTransfer(
+                xfer_asset=Asset(asset_id),
+                asset_receiver=recipient,
+                asset_amount=withdrawal,
                 fee=0
             ).submit()
 
+        pay_fee(asset_id, fee, fee_recipient, UInt64(NULLIFIER_MBR))
+
         # Save the change commitment, unless no_change is set or the tree is full
         if not no_change.native:
-            assert self.tree_not_full(), "Tree is full"
-            self.update_tree_with(unspent_commitment)
-            assert self.tree_not_full(), "Tree is full after adding unspent commitment"
-            self.update_tree_with(spend_commitment)
+            self.add_outputs(unspent_commitment, spend_commitment)
 
         return (self.inserted_leaves_count - 2, self.root.copy())
 
+    @subroutine
+    def add_outputs(self, unspent_commitment: Bytes32, spend_commitment: Bytes32) -> None:
+        """Add the unspent and spend commitments to the tree, fail if it is full"""
+        assert self.tree_not_full(), "Tree is full"
+        self.update_tree_with(unspent_commitment)
+        assert self.tree_not_full(), "Tree is full after adding unspent commitment"
+        self.update_tree_with(spend_commitment)
+
+    @subroutine
+    def add_outflow(self, asset_id: UInt64, amount: UInt64) -> None:
+        """Add amount spent from notes of asset_id to the current withdrawal window, fail
+           if it exceeds WITHDRAWAL_WINDOW_MAXIMUM or if asset_id is an ASA with caps"""
+        assert_algo_with_caps(asset_id)
+        if WITHDRAWAL_WINDOW_MAXIMUM > 0:
+            if Global.round >= self.window_start + WITHDRAWAL_WINDOW_ROUNDS:
+                self.window_start = Global.round - Global.round % WITHDRAWAL_WINDOW_ROUNDS
+                self.window_outflow = UInt64(0)
+            self.window_outflow += amount
+            assert self.window_outflow <= WITHDRAWAL_WINDOW_MAXIMUM, (
+                "Withdrawal window cap exceeded")
+
     @subroutine
     def tree_not_full(self) -> bool:
         """Check if the tree is full"""
@@ -298,7 +598,75 @@ def valid_root(root: Bytes32) -> bool:
             return True
     return False
 
+@subroutine
+def nullify(nullifier: Bytes32, root: Bytes32) -> None:
+    """Add the nullifier to the spent nullifiers and check the root the note was
+       proven against, fail if the nullifier already exists or the root is invalid"""
+    assert op.Box.create(nullifier.bytes, 0), "Nullifier already exists"
+    assert valid_root(root), "Invalid root"
+
+@subroutine
+def assert_algo_with_caps(asset_id: UInt64) -> None:
+    """Fail if asset_id is an ASA and any cap is set, the caps covering Algo only"""
+    if DEPOSIT_MAXIMUM_AMOUNT > 0 or TVL_MAXIMUM > 0 or WITHDRAWAL_WINDOW_MAXIMUM > 0:
+        assert asset_id == 0, "ASAs are disabled with caps"
+
+@subroutine
+def pay_fee(asset_id: UInt64, fee: UInt64, fee_recipient: Account, mbr: UInt64) -> None:
+    """Pay the fee of spent notes to fee_recipient as described in `withdraw`, mbr is
+       the MBR of their nullifier boxes"""
+    if asset_id == 0:
+        # Check the fee is not less than the MBR for the nullifier boxes
+        assert fee >= mbr, "Fee too low"
+
+        # Transfer any extra fee to fee_recipient (e.g., the TSS)
+        if fee > mbr:
+            itxn.Payment(
+                receiver=fee_recipient,
+                amount=fee - mbr,
+                fee=0
+            ).submit()
+    else:
+        # Check the fee recipient pays the MBR for the nullifier boxes
+        mbr_txn = py.gtxn.PaymentTransaction(op.Txn.group_index + 1)
+        assert mbr_txn.receiver == Global.current_application_address, "Wrong receiver"
+        assert mbr_txn.amount == mbr, "Incorrect nullifier MBR amount"
+        assert mbr_txn.sender == fee_recipient, "MBR not paid by the fee recipient"
+
+        if fee > 0:
+            itxn.AssetTransfer(
+                xfer_asset=Asset(asset_id),
+                asset_receiver=fee_recipient,
+                asset_amount=fee,
+                fee=0
+            ).submit()
+
+@subroutine
+def address_mod(account: Account) -> Bytes32:
+    """Return the address of account mod curve_mod, as computed by frontends for the
+       public inputs"""
+    return Bytes32.from_bytes(
+        py.op.bzero(32)
+        |
+        (py.BigUInt.from_bytes(account.bytes) % CURVE_MOD).bytes
+    )
+
+@subroutine
+def authorized_by(owner: Account) -> bool:
+    """Check if owner authorized the group: it is the sender of one of its transactions.
+       A call to the app of an app account does not count, since anyone can make it"""
+    for i in urange(Global.group_size):
+        if py.gtxn.Transaction(i).sender == owner:
+            return True
+    return False
+
+@subroutine
+def depositor_key(depositor: Address) -> Bytes:
+    """Return the key of the allowlist box of depositor"""
+    return Bytes(b'd') + depositor.bytes
+
 @subroutine
 def value_from_Bytes32(amount: Bytes32) -> UInt64:
-    """Convert an amount encoded in a Bytes32 to a UInt64"""
+    """Convert an amount encoded in a Bytes32 to a UInt64, which the amount must fit"""
+    assert amount.bytes[0:24] == op.bzero(24), "Amount overflows uint64"
     return op.btoi(amount.bytes[24:32])
@@ -9,7 +9,7 @@ def TSS() -> bool:
        The treasury smart signature (TSS) can be used to sign withdrawal transactions so that
        a zero-balance address can receive the funds.
        Can be invoked to sign an app call to the main contract:
-       -  withdraw method (mode 1).
+       -  withdraw, withdraw_from_address, transfer or join_split method (mode 1).
        -  noop method, to increase the opcode budget (mode 2)
     """
 
@@ -18,14 +18,22 @@ def TSS() -> bool:
 
     # mode 1: sign a withdrawal transaction
     # check that:
-    # - previous transaction is a call to the main contract withdraw method
+    # - previous transaction is a call to the main contract withdraw, withdraw_from_address,
+    #   transfer or join_split method
     # - current transaction is an app call to the main contract noop method
     #
     # The fee is not checked, since the TSS holds no funds it will be able to pay txn fees
     # only if the TSS is funded in the same transaction group (i.e., by the main contract's
     # withdraw method inner transaction)
     if is_app_call_to(prevTxn, arc4_signature(
-        "withdraw(byte[32][],byte[32][],account,account,bool)(uint64,byte[32])")):
+        "withdraw(byte[32][],byte[32][],account,account,bool)(uint64,byte[32])")) or (
+        is_app_call_to(prevTxn, arc4_signature(
+        "withdraw_from_address(byte[32][],byte[32][],account,account,bool,account)"
+        "(uint64,byte[32])"))) or (
+        is_app_call_to(prevTxn, arc4_signature(
+        "transfer(byte[32][],byte[32][],account)(uint64,byte[32])"))) or (
+        is_app_call_to(prevTxn, arc4_signature(
+        "join_split(byte[32][],byte[32][],account)(uint64,byte[32])"))):
         assert is_app_call_to(currentTxn, arc4_signature("noop(uint64)void")), "wrong method"
         return True
 
@@ -0,0 +1,157 @@
+package setup
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/json"
+	"errors"
+	"log"
+	"os"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+	"github.com/joe-p/Mithras-Protocol/deployed"
+
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// budgetMarginPercent is the margin added to the opcode budgets measured by simulation
+const budgetMarginPercent = 10
+
+// spendMethods are the methods sharing WITHDRAWAL_OPCODE_BUDGET_OPUP
+var spendMethods = []string{client.WithDrawalMethod, client.AddressWithdrawalMethod,
+	client.TransferMethod, client.JoinSplitMethod}
+
+// EstimateCosts measures the cost of the methods of the app deployed on network by
+// sending from the default account a deposit, a withdrawal, a transfer and a
+// join-split, and writes their client.Estimate to EstimatesPath. The next CreateApp
+// sets the opcode budgets of the contract from them (see opcodeBudgets); delete the
+// file to go back to the config defaults.
+func EstimateCosts(network deployed.Network) {
+	avm.Initialize(network)
+	ctx := context.Background()
+
+	f, err := client.NewFrontend(network, avm.GetAlgodClient())
+	if err != nil {
+		log.Fatalf("Error creating frontend: %v", err)
+	}
+	scanner, err := f.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		log.Fatalf("Error creating scanner: %v", err)
+	}
+	if _, err := scanner.Scan(ctx); err != nil {
+		log.Fatalf("Error syncing the tree: %v", err)
+	}
+
+	account := avm.GetDefaultAccount()
+	key, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		log.Fatalf("Error generating key: %v", err)
+	}
+	receivingKey := client.ReceivingKey(*key)
+
+	var deposits [2]*client.Deposit
+	for i := range deposits {
+		deposits[i], err = f.SendDeposit(account, 10*1e6, receivingKey, *key)
+		if err != nil {
+			log.Fatalf("Error making deposit: %v", err)
+		}
+	}
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1e6,
+		FromNote:  deposits[0].Note,
+	}, key, receivingKey)
+	if err != nil {
+		log.Fatalf("Error making withdrawal: %v", err)
+	}
+	transfer, err := f.SendTransfer(&client.TransferOpts{
+		Amount:   1e6,
+		FromNote: withdrawal.Note,
+	}, key, receivingKey)
+	if err != nil {
+		log.Fatalf("Error making transfer: %v", err)
+	}
+	fee := uint64(config.WithdrawalMinFeeMultiplier*transaction.MinTxnFee +
+		circuits.JoinSplitInputs*config.NullifierMbr)
+	fromNotes := []*client.Note{deposits[1].Note, transfer.Note}
+	_, err = f.SendJoinSplit(&client.JoinSplitOpts{
+		Fee:       fee,
+		FromNotes: fromNotes,
+		Outputs: []client.JoinSplitOutput{
+			{Amount: fromNotes[0].Amount + fromNotes[1].Amount - fee, Pubkey: receivingKey},
+			{Amount: 0, Pubkey: receivingKey},
+		},
+	}, key)
+	if err != nil {
+		log.Fatalf("Error making join-split: %v", err)
+	}
+
+	estimates := map[string]*client.Estimate{}
+	for _, method := range []string{client.DepositMethod, client.WithDrawalMethod,
+		client.TransferMethod, client.JoinSplitMethod} {
+
+		e := f.Estimate(method)
+		if e == nil {
+			log.Fatalf("No estimate of method %s", method)
+		}
+		estimates[method] = e
+		log.Printf("%s: app budget %d, logicsig budget %d, %d inner txns, min %d noops, "+
+			"min fee %d (multiplier %d)", method, e.AppBudget, e.LogicSigBudget,
+			e.InnerTxns, e.MinNoops, e.MinFee, e.MinFee/transaction.MinTxnFee)
+	}
+	jsonData, err := json.MarshalIndent(estimates, "", "    ")
+	if err != nil {
+		log.Fatalf("Error marshalling estimates: %v", err)
+	}
+	if err := os.WriteFile(EstimatesPath, jsonData, 0644); err != nil {
+		log.Fatalf("Error writing estimates: %v", err)
+	}
+	log.Println("Wrote estimates to", EstimatesPath)
+}
+
+// opcodeBudgets returns the DEPOSIT_OPCODE_BUDGET_OPUP and WITHDRAWAL_OPCODE_BUDGET_OPUP
+// of the contract: the app budgets measured by EstimateCosts plus a margin if
+// EstimatesPath exists, the config defaults otherwise. The withdrawal budget is the
+// largest of the spending methods.
+func opcodeBudgets() (deposit, withdrawal int) {
+	deposit, withdrawal = config.DepositOpcodeBudgetOpUp, config.WithdrawalOpcodeBudgetOpUp
+
+	data, err := os.ReadFile(EstimatesPath)
+	if errors.Is(err, os.ErrNotExist) {
+		return deposit, withdrawal
+	}
+	estimates := map[string]*client.Estimate{}
+	if err == nil {
+		err = json.Unmarshal(data, &estimates)
+	}
+	if err != nil {
+		log.Printf("Error reading estimates, using the default opcode budgets: %v\n", err)
+		return deposit, withdrawal
+	}
+
+	if e := estimates[client.DepositMethod]; e != nil {
+		deposit = withMargin(e.AppBudget)
+	}
+	spend := 0
+	for _, method := range spendMethods {
+		if e := estimates[method]; e != nil {
+			spend = max(spend, withMargin(e.AppBudget))
+		}
+	}
+	if spend > 0 {
+		withdrawal = spend
+	}
+	log.Printf("Opcode budgets from %s: deposit %d, withdrawal %d\n", EstimatesPath,
+		deposit, withdrawal)
+	return deposit, withdrawal
+}
+
+// withMargin returns budget plus budgetMarginPercent, rounded up to a hundred
+func withMargin(budget uint64) int {
+	b := int(budget) * (100 + budgetMarginPercent) / 100
+	return (b + 99) / 100 * 100
+}
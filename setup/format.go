@@ -20,6 +20,14 @@ func formatWithUnderscores(num int) string {
 	return result.String()
 }
 
+// formatBool formats a bool as a python literal
+func formatBool(b bool) string {
+	if b {
+		return "True"
+	}
+	return "False"
+}
+
 // formatAsStringConcatenation converts a [][]byte to a string that looks like:
 // "hex1"
 // + "hex2"
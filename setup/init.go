@@ -16,10 +16,20 @@ const (
 	TssName                           = "TSS"
 	DepositVerifierName               = "DepositVerifier"
 	WithdrawalVerifierName            = "WithdrawalVerifier"
+	AddressWithdrawalVerifierName     = "AddressWithdrawalVerifier"
 	DepositCircuitCompiledFilename    = "CompiledDepositCircuit.bin"
 	WithdrawalCircuitCompiledFilename = "CompiledWithdrawalCircuit.bin"
 	AppFilename                       = "App.json"
 	TreeConfigFilename                = "TreeConfig.json"
+	EstimatesFilename                 = "Estimates.json"
+
+	AddressWithdrawalCircuitCompiledFilename = "CompiledAddressWithdrawalCircuit.bin"
+
+	TransferVerifierName            = "TransferVerifier"
+	TransferCircuitCompiledFilename = "CompiledTransferCircuit.bin"
+
+	JoinSplitVerifierName            = "JoinSplitVerifier"
+	JoinSplitCircuitCompiledFilename = "CompiledJoinSplitCircuit.bin"
 )
 
 var (
@@ -35,6 +45,14 @@ var (
 	WithdrawalVerifierTealPath     string
 	WithdrawalVerifierBytecodePath string
 	TreeConfigPath                 string
+	EstimatesPath                  string
+
+	AddressWithdrawalVerifierTealPath     string
+	AddressWithdrawalVerifierBytecodePath string
+	TransferVerifierTealPath              string
+	TransferVerifierBytecodePath          string
+	JoinSplitVerifierTealPath             string
+	JoinSplitVerifierBytecodePath         string
 )
 
 type CircuitData struct {
@@ -44,7 +62,8 @@ type CircuitData struct {
 	CompiledPath   string
 }
 
-var DepositCircuitData, WithdrawalCircuitData CircuitData
+var DepositCircuitData, WithdrawalCircuitData, AddressWithdrawalCircuitData CircuitData
+var TransferCircuitData, JoinSplitCircuitData CircuitData
 
 func init() {
 	_, filename, _, _ := runtime.Caller(0) // this file
@@ -62,11 +81,20 @@ func init() {
 	TssBytecodePath = filepath.Join(ArtefactsDirPath, TssName+".tok")
 	AppPath = filepath.Join(ArtefactsDirPath, AppFilename)
 	TreeConfigPath = filepath.Join(ArtefactsDirPath, TreeConfigFilename)
+	EstimatesPath = filepath.Join(ArtefactsDirPath, EstimatesFilename)
 	AppSchemaPath = filepath.Join(ArtefactsDirPath, MainContractName+".arc32.json")
 	DeppositVerifierTealPath = filepath.Join(ArtefactsDirPath, DepositVerifierName+".teal")
 	DepositVerifierBytecodePath = filepath.Join(ArtefactsDirPath, DepositVerifierName+".tok")
 	WithdrawalVerifierTealPath = filepath.Join(ArtefactsDirPath, WithdrawalVerifierName+".teal")
 	WithdrawalVerifierBytecodePath = filepath.Join(ArtefactsDirPath, WithdrawalVerifierName+".tok")
+	AddressWithdrawalVerifierTealPath = filepath.Join(ArtefactsDirPath,
+		AddressWithdrawalVerifierName+".teal")
+	AddressWithdrawalVerifierBytecodePath = filepath.Join(ArtefactsDirPath,
+		AddressWithdrawalVerifierName+".tok")
+	TransferVerifierTealPath = filepath.Join(ArtefactsDirPath, TransferVerifierName+".teal")
+	TransferVerifierBytecodePath = filepath.Join(ArtefactsDirPath, TransferVerifierName+".tok")
+	JoinSplitVerifierTealPath = filepath.Join(ArtefactsDirPath, JoinSplitVerifierName+".teal")
+	JoinSplitVerifierBytecodePath = filepath.Join(ArtefactsDirPath, JoinSplitVerifierName+".tok")
 
 	DepositCircuitData = CircuitData{
 		Circuit:        &circuits.DepositCircuit{},
@@ -81,4 +109,26 @@ func init() {
 		DefinitionPath: circuits.WithdrawalCircuitPackageName,
 		CompiledPath:   filepath.Join(ArtefactsDirPath, WithdrawalCircuitCompiledFilename),
 	}
+
+	AddressWithdrawalCircuitData = CircuitData{
+		Circuit:        &circuits.AddressWithdrawalCircuit{},
+		VerifierName:   AddressWithdrawalVerifierName,
+		DefinitionPath: circuits.AddressWithdrawalCircuitPackageName,
+		CompiledPath: filepath.Join(ArtefactsDirPath,
+			AddressWithdrawalCircuitCompiledFilename),
+	}
+
+	TransferCircuitData = CircuitData{
+		Circuit:        &circuits.TransferCircuit{},
+		VerifierName:   TransferVerifierName,
+		DefinitionPath: circuits.TransferCircuitPackageName,
+		CompiledPath:   filepath.Join(ArtefactsDirPath, TransferCircuitCompiledFilename),
+	}
+
+	JoinSplitCircuitData = CircuitData{
+		Circuit:        &circuits.JoinSplitCircuit{},
+		VerifierName:   JoinSplitVerifierName,
+		DefinitionPath: circuits.JoinSplitCircuitPackageName,
+		CompiledPath:   filepath.Join(ArtefactsDirPath, JoinSplitCircuitCompiledFilename),
+	}
 }
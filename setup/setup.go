@@ -11,6 +11,7 @@ import (
 	"strconv"
 
 	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/circuits"
 	"github.com/joe-p/Mithras-Protocol/config"
 	"github.com/joe-p/Mithras-Protocol/deployed"
 
@@ -29,7 +30,7 @@ import (
 /*
 
 To set up the application on the AVM we need to follow these steps:
-  1. Generate the deposit and withdrawal verifiers from the circuits
+  1. Generate the deposit, withdrawal, transfer and join-split verifiers from the circuits
   2. Update the APP smart contract and TSS logicsig based on the configuration
   3. Compile APP with the verifiers' addresses and deploy it to the network
   4. Compile TSS with the APP id
@@ -43,9 +44,15 @@ there will be the files that a frontend need to interact with the application:
   * TSS.tok							: compiled TSS logicsig
   * DepositVerifier.tok				: compiled deposit verifier logicsig
   * WithdrawalVerifier.tok			: compiled withdrawal verifier logicsig
+  * AddressWithdrawalVerifier.tok	: compiled address withdrawal verifier logicsig
+  * TransferVerifier.tok			: compiled transfer verifier logicsig
+  * JoinSplitVerifier.tok			: compiled join-split verifier logicsig
   * TreeConfig.json					: Tree configuration (depth, zero value, zero hashes)
   * CompiledDepositCircuit.bin 		: serialized compiled deposit circuit
   * CompiledWithdrawalCircuit.bin	: serialized compiled withdrawal circuit
+  * CompiledAddressWithdrawalCircuit.bin	: serialized compiled address withdrawal circuit
+  * CompiledTransferCircuit.bin		: serialized compiled transfer circuit
+  * CompiledJoinSplitCircuit.bin	: serialized compiled join-split circuit
 
 The serialized compiled circuits can be deserialized with AlgoPlonk, alternatively frontends
 can use directly the circuit definitions in the circuits package.
@@ -79,10 +86,15 @@ func CreateApp(network deployed.Network) {
 
 	depositVerifierAdress := generateVerifier(config.Curve, &DepositCircuitData)
 	withdrawalVerifierAddress := generateVerifier(config.Curve, &WithdrawalCircuitData)
+	addressWithdrawalVerifierAddress := generateVerifier(config.Curve,
+		&AddressWithdrawalCircuitData)
+	transferVerifierAddress := generateVerifier(config.Curve, &TransferCircuitData)
+	joinSplitVerifierAddress := generateVerifier(config.Curve, &JoinSplitCircuitData)
 
 	updateConstantsInSmartContracts()
 
-	compileMainContract(depositVerifierAdress, withdrawalVerifierAddress)
+	compileMainContract(depositVerifierAdress, withdrawalVerifierAddress,
+		addressWithdrawalVerifierAddress, transferVerifierAddress, joinSplitVerifierAddress)
 
 	appId := deployMainContract()
 	tssBytecode := setupTSS(appId)
@@ -151,10 +163,14 @@ func generateVerifier(curve ecc.ID, c *CircuitData) types.Address {
 func compileMainContract(
 	depositVerifierAddress types.Address,
 	withdrawalVerifierAddress types.Address,
+	addressWithdrawalVerifierAddress types.Address,
+	transferVerifierAddress types.Address,
+	joinSplitVerifierAddress types.Address,
 ) {
 	approvalTealPath := filepath.Join(ArtefactsDirPath, MainContractName+".approval.teal")
 	approvalSources := []string{MainContractSourcePath, DeppositVerifierTealPath,
-		WithdrawalVerifierTealPath}
+		WithdrawalVerifierTealPath, AddressWithdrawalVerifierTealPath, TransferVerifierTealPath,
+		JoinSplitVerifierTealPath}
 	clearTealPath := filepath.Join(ArtefactsDirPath, MainContractName+".clear.teal")
 
 	recompile := utils.ShouldRecompile(approvalTealPath, approvalSources...) ||
@@ -171,6 +187,12 @@ func compileMainContract(
 				hex.EncodeToString(depositVerifierAddress[:]),
 			"TMPL_WITHDRAWAL_VERIFIER_ADDRESS": "0x" +
 				hex.EncodeToString(withdrawalVerifierAddress[:]),
+			"TMPL_ADDRESS_WITHDRAWAL_VERIFIER_ADDRESS": "0x" +
+				hex.EncodeToString(addressWithdrawalVerifierAddress[:]),
+			"TMPL_TRANSFER_VERIFIER_ADDRESS": "0x" +
+				hex.EncodeToString(transferVerifierAddress[:]),
+			"TMPL_JOIN_SPLIT_VERIFIER_ADDRESS": "0x" +
+				hex.EncodeToString(joinSplitVerifierAddress[:]),
 		}
 		for _, path := range []string{approvalTealPath, clearTealPath} {
 			err = replaceInFile(path, substitutions)
@@ -332,20 +354,30 @@ func writeTreeConfig() {
 	}
 }
 
-// updateConstantsInSmartContracts updates the constants in the smart contracts files
+// updateConstantsInSmartContracts updates the constants in the smart contracts files,
+// with the opcode budgets tuned by EstimateCosts if it was run
 func updateConstantsInSmartContracts() {
+	depositBudget, withdrawalBudget := opcodeBudgets()
 	changesMainContract := [][2]string{
 		{"CURVE_MOD", config.Curve.ScalarField().String()},
 		{"DEPOSIT_MINIMUM_AMOUNT", formatWithUnderscores(config.DepositMinimumAmount) + " # 1 Algo"},
+		{"DEPOSIT_MAXIMUM_AMOUNT", formatWithUnderscores(config.DepositMaximumAmount)},
+		{"TVL_MAXIMUM", formatWithUnderscores(config.TvlMaximum)},
+		{"WITHDRAWAL_WINDOW_ROUNDS", formatWithUnderscores(config.WithdrawalWindowRounds)},
+		{"WITHDRAWAL_WINDOW_MAXIMUM", formatWithUnderscores(config.WithdrawalWindowMaximum)},
 		{"TREE_DEPTH", formatWithUnderscores(config.MerkleTreeLevels)},
 		{"MAX_LEAVES", formatWithUnderscores(1 << config.MerkleTreeLevels)},
 		{"ROOTS_COUNT", formatWithUnderscores(config.RootsCount)},
 		{"INITIAL_ROOT", "\"" +
 			hex.EncodeToString(config.Tree.ZeroHashes[config.MerkleTreeLevels]) + "\""},
-		{"DEPOSIT_OPCODE_BUDGET_OPUP", formatWithUnderscores(config.DepositOpcodeBudgetOpUp)},
-		{"WITHDRAWAL_OPCODE_BUDGET_OPUP",
-			formatWithUnderscores(config.WithdrawalOpcodeBudgetOpUp)},
+		{"DEPOSIT_OPCODE_BUDGET_OPUP", formatWithUnderscores(depositBudget)},
+		{"WITHDRAWAL_OPCODE_BUDGET_OPUP", formatWithUnderscores(withdrawalBudget)},
 		{"NULLIFIER_MBR", formatWithUnderscores(config.NullifierMbr)},
+		{"ASSET_OPT_IN_MBR", formatWithUnderscores(config.AssetOptInMbr)},
+		{"DEPOSIT_ALLOWLIST", formatBool(config.DepositAllowlist)},
+		{"DEPOSITOR_MBR", formatWithUnderscores(config.DepositorMbr)},
+		{"JOIN_SPLIT_INPUTS", formatWithUnderscores(circuits.JoinSplitInputs)},
+		{"JOIN_SPLIT_OUTPUTS", formatWithUnderscores(circuits.JoinSplitOutputs)},
 	}
 	err := changeValueInFile(MainContractSourcePath, changesMainContract)
 	if err != nil {
@@ -362,7 +394,10 @@ func updateConstantsInSmartContracts() {
 func exportSetupFiles(network deployed.Network) {
 	filepaths := []string{AppPath, TreeConfigPath, AppSchemaPath, TssBytecodePath,
 		DepositVerifierBytecodePath, WithdrawalVerifierBytecodePath, TreeConfigPath,
-		DepositCircuitData.CompiledPath, WithdrawalCircuitData.CompiledPath}
+		AddressWithdrawalVerifierBytecodePath, DepositCircuitData.CompiledPath,
+		WithdrawalCircuitData.CompiledPath, AddressWithdrawalCircuitData.CompiledPath,
+		TransferVerifierBytecodePath, TransferCircuitData.CompiledPath,
+		JoinSplitVerifierBytecodePath, JoinSplitCircuitData.CompiledPath}
 	for _, path := range filepaths {
 		err := copyFile(path, filepath.Join(network.DirPath(), filepath.Base(path)))
 		if err != nil {
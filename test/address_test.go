@@ -0,0 +1,92 @@
+package test
+
+import (
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+)
+
+func TestAddressDepositWithdraw(t *testing.T) {
+	depositor := crypto.GenerateAccount()
+	err := avm.EnsureFunded(depositor.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	ownerAccount := crypto.GenerateAccount()
+	err = avm.EnsureFunded(ownerAccount.Address.String(), 1*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	noteKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	owner := &client.AddressOwner{
+		Address:    ownerAccount.Address,
+		Signer:     transaction.BasicAccountTransactionSigner{Account: ownerAccount},
+		NotePubkey: client.ReceivingKey(*noteKey),
+	}
+
+	// the depositor sends to an address without knowing a key of its own
+	depositAmount := uint64(10 * 1e6)
+	deposit, err := f.SendAddressDeposit(&depositor, depositAmount, 0, owner.Address,
+		owner.NotePubkey)
+	if err != nil {
+		t.Fatalf("Error making address deposit: %s", err)
+	}
+	if deposit.Note.OwnerAddress != owner.Address {
+		t.Fatalf("Deposit note not owned by the address")
+	}
+
+	// an eddsa withdrawal cannot spend an address note
+	_, err = f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: depositor.Address,
+		Amount:    1 * 1e6,
+		FromNote:  deposit.Note,
+	}, noteKey, client.ReceivingKey(*noteKey))
+	if err == nil {
+		t.Fatalf("Expected an eddsa withdrawal of an address note to fail")
+	}
+
+	// nor can an address withdrawal not authorized by the owner
+	impostor := &client.AddressOwner{
+		Address:    owner.Address,
+		Signer:     transaction.BasicAccountTransactionSigner{Account: depositor},
+		NotePubkey: owner.NotePubkey,
+	}
+	_, err = f.SendAddressWithdrawal(&client.WithdrawalOpts{
+		Recipient: depositor.Address,
+		Amount:    1 * 1e6,
+		FromNote:  deposit.Note,
+	}, impostor, client.ReceivingKey(*noteKey))
+	if err == nil {
+		t.Fatalf("Expected a withdrawal not signed by the owner to fail")
+	}
+
+	withdrawalAmount := uint64(2 * 1e6)
+	withdrawal, err := f.SendAddressWithdrawal(&client.WithdrawalOpts{
+		Recipient: owner.Address,
+		Amount:    withdrawalAmount,
+		FromNote:  deposit.Note,
+	}, owner, client.ReceivingKey(*noteKey))
+	if err != nil {
+		t.Fatalf("Error making address withdrawal: %s", err)
+	}
+	if withdrawal.Note.OwnerAddress != owner.Address {
+		t.Fatalf("Change note not owned by the address")
+	}
+
+	// the change can be spent again by the owner
+	_, err = f.SendAddressWithdrawal(&client.WithdrawalOpts{
+		Recipient: owner.Address,
+		Amount:    withdrawalAmount,
+		FromNote:  withdrawal.Note,
+	}, owner, client.ReceivingKey(*noteKey))
+	if err != nil {
+		t.Fatalf("Error withdrawing the change: %s", err)
+	}
+}
@@ -0,0 +1,71 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestDepositAllowlist(t *testing.T) {
+	if !config.DepositAllowlist {
+		t.Skip("No deposit allowlist configured")
+	}
+	ctx := context.Background()
+	admin := avm.GetDefaultAccount() // the app creator
+	depositor := crypto.GenerateAccount()
+	other := crypto.GenerateAccount()
+	for _, account := range []crypto.Account{depositor, other} {
+		err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+		if err != nil {
+			t.Fatalf("Error funding account: %s", err)
+		}
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+
+	if err := f.AddDepositor(&other, depositor.Address); err == nil {
+		t.Fatalf("Expected adding a depositor by another account than the admin to fail")
+	}
+	if err := f.AddDepositor(admin, depositor.Address); err != nil {
+		t.Fatalf("Error adding depositor: %s", err)
+	}
+	if err := f.AddDepositor(admin, depositor.Address); err == nil {
+		t.Fatalf("Expected adding a depositor twice to fail")
+	}
+	allowed, err := f.DepositorAllowed(ctx, depositor.Address)
+	if err != nil || !allowed {
+		t.Fatalf("Expected depositor to be allowed: %v", err)
+	}
+
+	if _, err := f.SendDeposit(&depositor, 10*1e6, client.ReceivingKey(*privKey), *privKey); err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	if _, err := f.SendDeposit(&other, 10*1e6, client.ReceivingKey(*privKey), *privKey); err == nil {
+		t.Fatalf("Expected a deposit by a depositor not allowed to fail")
+	}
+
+	// hand the admin role over and back
+	if err := f.SetAdmin(admin, other.Address); err != nil {
+		t.Fatalf("Error setting admin: %s", err)
+	}
+	if err := f.RemoveDepositor(admin, depositor.Address); err == nil {
+		t.Fatalf("Expected removing a depositor by the former admin to fail")
+	}
+	if err := f.RemoveDepositor(&other, depositor.Address); err != nil {
+		t.Fatalf("Error removing depositor: %s", err)
+	}
+	if err := f.SetAdmin(&other, admin.Address); err != nil {
+		t.Fatalf("Error setting admin: %s", err)
+	}
+	allowed, err = f.DepositorAllowed(ctx, depositor.Address)
+	if err != nil || allowed {
+		t.Fatalf("Expected depositor to be removed: %v", err)
+	}
+}
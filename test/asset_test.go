@@ -0,0 +1,123 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// createTestAsset creates an asset with total supply held by creator and returns its id
+func createTestAsset(creator *crypto.Account, total uint64) (uint64, error) {
+	algodClient := avm.GetAlgodClient()
+	sp, err := algodClient.SuggestedParams().Do(context.Background())
+	if err != nil {
+		return 0, err
+	}
+	txn, err := transaction.MakeAssetCreateTxn(creator.Address.String(), nil, sp, total, 0,
+		false, "", "", "", "", "TEST", "Mithras test asset", "", "")
+	if err != nil {
+		return 0, err
+	}
+	var atc = transaction.AtomicTransactionComposer{}
+	err = atc.AddTransaction(transaction.TransactionWithSigner{
+		Txn:    txn,
+		Signer: transaction.BasicAccountTransactionSigner{Account: *creator},
+	})
+	if err != nil {
+		return 0, err
+	}
+	res, err := atc.Execute(algodClient, context.Background(), 4)
+	if err != nil {
+		return 0, err
+	}
+	info, _, err := algodClient.PendingTransactionInformation(res.TxIDs[0]).Do(context.Background())
+	if err != nil {
+		return 0, err
+	}
+	return info.AssetIndex, nil
+}
+
+// assetBalance returns the amount of assetId held by address
+func assetBalance(address types.Address, assetId uint64) (uint64, error) {
+	info, err := avm.GetAlgodClient().AccountAssetInformation(address.String(), assetId).
+		Do(context.Background())
+	if err != nil {
+		return 0, err
+	}
+	return info.AssetHolding.Amount, nil
+}
+
+func TestAssetDepositWithdraw(t *testing.T) {
+	if config.CapsEnabled {
+		t.Skip("ASAs are disabled with caps")
+	}
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	assetId, err := createTestAsset(&account, 1_000_000)
+	if err != nil {
+		t.Fatalf("Error creating asset: %s", err)
+	}
+	if err := f.OptInAsset(&account, assetId); err != nil {
+		t.Fatalf("Error opting the app in the asset: %s", err)
+	}
+	if err := f.OptInAsset(&account, assetId); err == nil {
+		t.Fatalf("Expected a second opt in to fail")
+	}
+
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendAssetDeposit(&account, 1000, assetId, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making asset deposit: %s", err)
+	}
+	if deposit.Note.AssetId != assetId {
+		t.Fatalf("Deposit note asset %d, expected %d", deposit.Note.AssetId, assetId)
+	}
+
+	// the account withdraws to itself and pays the fees, so it is both the recipient
+	// and the fee recipient
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient:    account.Address,
+		FeeRecipient: account.Address,
+		FeeSigner:    transaction.BasicAccountTransactionSigner{Account: account},
+		Amount:       300,
+		Fee:          10,
+		FromNote:     deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error making asset withdrawal: %s", err)
+	}
+	if withdrawal.Note.Amount != 690 || withdrawal.Note.AssetId != assetId {
+		t.Fatalf("Expected a change note of 690 of asset %d, got %d of asset %d",
+			assetId, withdrawal.Note.Amount, withdrawal.Note.AssetId)
+	}
+	balance, err := assetBalance(account.Address, assetId)
+	if err != nil {
+		t.Fatalf("Error reading asset balance: %s", err)
+	}
+	if balance != 1_000_000-1000+300+10 {
+		t.Fatalf("Unexpected asset balance %d", balance)
+	}
+
+	// an asset withdrawal cannot use the TSS to pay the fees
+	_, err = f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    100,
+		FromNote:  withdrawal.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err == nil {
+		t.Fatalf("Expected an asset withdrawal without fee recipient to fail")
+	}
+}
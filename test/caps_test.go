@@ -0,0 +1,83 @@
+package test
+
+import (
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestDepositCap(t *testing.T) {
+	if config.DepositMaximumAmount == 0 {
+		t.Skip("No deposit cap configured")
+	}
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 2*config.DepositMaximumAmount+10*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	_, err = f.SendDeposit(&account, config.DepositMaximumAmount+1, client.ReceivingKey(*privKey),
+		*privKey)
+	if err == nil {
+		t.Fatalf("Expected a deposit above the cap to fail")
+	}
+	_, err = f.SendDeposit(&account, config.DepositMaximumAmount, client.ReceivingKey(*privKey),
+		*privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+}
+
+func TestWithdrawalWindowCap(t *testing.T) {
+	if config.WithdrawalWindowMaximum == 0 {
+		t.Skip("No withdrawal window cap configured")
+	}
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 2*config.WithdrawalWindowMaximum+10*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&account, 2*config.WithdrawalWindowMaximum,
+		client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	// the withdrawal and its fee are more than the window allows
+	_, err = f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    config.WithdrawalWindowMaximum,
+		FromNote:  deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err == nil {
+		t.Fatalf("Expected a withdrawal above the window cap to fail")
+	}
+}
+
+func TestAssetsDisabledWithCaps(t *testing.T) {
+	if !config.CapsEnabled {
+		t.Skip("No cap configured")
+	}
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 10*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	assetId, err := createTestAsset(&account, 1_000_000)
+	if err != nil {
+		t.Fatalf("Error creating asset: %s", err)
+	}
+	if err := f.OptInAsset(&account, assetId); err == nil {
+		t.Fatalf("Expected opting the app in an asset with caps to fail")
+	}
+}
@@ -0,0 +1,138 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestDiscoverNotes(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	depositAmount := uint64(10 * 1e6)
+	deposit, err := f.SendDeposit(&account, depositAmount, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error making withdrawal: %s", err)
+	}
+
+	wallet := NewAppFrontend()
+	scanner, err := wallet.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+
+	discovery, err := wallet.DiscoverNotes(context.Background(), events, *privKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Spent) != 1 || discovery.Spent[0].InsertedIndex != deposit.Note.InsertedIndex {
+		t.Fatalf("Expected the deposit note to be spent, got %d spent notes",
+			len(discovery.Spent))
+	}
+	if len(discovery.Unspent) != 1 || discovery.Balances[0] != withdrawal.Note.Amount {
+		t.Fatalf("Expected balance %d in 1 note, got %d in %d notes",
+			withdrawal.Note.Amount, discovery.Balances[0], len(discovery.Unspent))
+	}
+	if discovery.Unspent[0].InsertedIndex != withdrawal.Note.InsertedIndex {
+		t.Fatalf("Change note index mismatch: got %d, expected %d",
+			discovery.Unspent[0].InsertedIndex, withdrawal.Note.InsertedIndex)
+	}
+
+	// a different key discovers nothing
+	otherKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	discovery, err = wallet.DiscoverNotes(context.Background(), events, *otherKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Balances) != 0 || len(discovery.Unspent)+len(discovery.Spent) != 0 {
+		t.Fatalf("Expected no notes for a different key")
+	}
+}
+
+func TestDiscoverWithdrawalPayment(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	senderKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	receiverKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&account, 10*1e6, client.ReceivingKey(*senderKey), *senderKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	spendAmount := uint64(2 * 1e6)
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient:   account.Address,
+		Amount:      1 * 1e6,
+		SpendAmount: spendAmount,
+		FromNote:    deposit.Note,
+	}, senderKey, client.ReceivingKey(*receiverKey))
+	if err != nil {
+		t.Fatalf("Error making withdrawal: %s", err)
+	}
+
+	wallet := NewAppFrontend()
+	scanner, err := wallet.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	discovery, err := wallet.DiscoverNotes(context.Background(), events, *receiverKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Unspent) != 1 || discovery.Balances[0] != spendAmount {
+		t.Fatalf("Expected the receiver to discover %d in 1 note, got %d in %d notes",
+			spendAmount, discovery.Balances[0], len(discovery.Unspent))
+	}
+	if discovery.Unspent[0].InsertedIndex != withdrawal.SentNote.InsertedIndex {
+		t.Fatalf("Payment note index mismatch: got %d, expected %d",
+			discovery.Unspent[0].InsertedIndex, withdrawal.SentNote.InsertedIndex)
+	}
+
+	// the receiver can spend the payment
+	_, err = wallet.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  discovery.Unspent[0],
+	}, receiverKey, client.ReceivingKey(*receiverKey))
+	if err != nil {
+		t.Fatalf("Error withdrawing the payment note: %s", err)
+	}
+}
@@ -5,6 +5,7 @@ import (
 	"testing"
 
 	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+	"github.com/joe-p/Mithras-Protocol/client"
 	"github.com/joe-p/Mithras-Protocol/encrypt"
 )
 
@@ -43,6 +44,42 @@ func TestECIESEncryptDecrypt(t *testing.T) {
 	}
 }
 
+func TestECIESHKDFEncryptDecrypt(t *testing.T) {
+	privKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatalf("Failed to generate key: %v", err)
+	}
+	ephemeralPriv, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatalf("Failed to generate ephemeral key: %v", err)
+	}
+	testData := []byte("test secret data for k or r")
+
+	encrypted, err := encrypt.ECIESEncryptHKDF(testData, privKey.PublicKey, *ephemeralPriv)
+	if err != nil {
+		t.Fatalf("Failed to encrypt: %v", err)
+	}
+	decrypted, err := encrypt.ECIESDecryptHKDF(encrypted, ephemeralPriv.PublicKey, *privKey)
+	if err != nil {
+		t.Fatalf("Failed to decrypt: %v", err)
+	}
+	if string(decrypted) != string(testData) {
+		t.Fatalf("Decrypted data doesn't match original. Got %s, expected %s", string(decrypted), string(testData))
+	}
+
+	// the key is bound to the public keys, so the scrypt variant cannot decrypt it
+	if _, err := encrypt.ECIESDecrypt(encrypted, ephemeralPriv.PublicKey, *privKey); err == nil {
+		t.Fatalf("Expected scrypt decryption of HKDF ciphertext to fail")
+	}
+	wrongKey, err := eddsa.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatalf("Failed to generate wrong key: %v", err)
+	}
+	if _, err := encrypt.ECIESDecryptHKDF(encrypted, ephemeralPriv.PublicKey, *wrongKey); err == nil {
+		t.Fatalf("Expected decryption with the wrong key to fail")
+	}
+}
+
 func TestNoteRecovery(t *testing.T) {
 	// Create a frontend
 	frontend := NewAppFrontend()
@@ -57,17 +94,20 @@ func TestNoteRecovery(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Failed to generate output key: %v", err)
 	}
-	outputPubKey := outputPrivKey.PublicKey
+	outputPubKey := client.ReceivingKey(*outputPrivKey)
 
 	// Create a note
 	amount := uint64(1000)
-	note, encryptedNote := frontend.NewNote(amount, *inputPrivKey, outputPubKey)
+	note, encryptedNote, err := frontend.NewNote(amount, *inputPrivKey, outputPubKey)
+	if err != nil {
+		t.Fatalf("Failed to create note: %v", err)
+	}
 
 	// Try to recover the note using the output private key
 	recoveredNote, err := frontend.RecoverNote(
 		encryptedNote,
 		*outputPrivKey,
-		note.insertedIndex,
+		note.InsertedIndex,
 	)
 	if err != nil {
 		t.Fatalf("Failed to recover note: %v", err)
@@ -78,15 +118,15 @@ func TestNoteRecovery(t *testing.T) {
 		t.Fatalf("Amount mismatch: got %d, expected %d", recoveredNote.Amount, note.Amount)
 	}
 
-	if string(recoveredNote.k) != string(note.k) {
+	if string(recoveredNote.K) != string(note.K) {
 		t.Fatalf("k value mismatch")
 	}
 
-	if string(recoveredNote.r) != string(note.r) {
+	if string(recoveredNote.R) != string(note.R) {
 		t.Fatalf("r value mismatch")
 	}
 
-	if string(recoveredNote.commitment) != string(note.commitment) {
+	if string(recoveredNote.Commitment) != string(note.Commitment) {
 		t.Fatalf("commitment mismatch")
 	}
 }
@@ -105,7 +145,7 @@ func TestNoteRecoveryWithWrongKey(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Failed to generate output key: %v", err)
 	}
-	outputPubKey := outputPrivKey.PublicKey
+	outputPubKey := client.ReceivingKey(*outputPrivKey)
 
 	// Generate a different private key (wrong key)
 	wrongPrivKey, err := eddsa.GenerateKey(rand.Reader)
@@ -115,13 +155,16 @@ func TestNoteRecoveryWithWrongKey(t *testing.T) {
 
 	// Create a note
 	amount := uint64(1000)
-	note, encryptedNote := frontend.NewNote(amount, *inputPrivKey, outputPubKey)
+	note, encryptedNote, err := frontend.NewNote(amount, *inputPrivKey, outputPubKey)
+	if err != nil {
+		t.Fatalf("Failed to create note: %v", err)
+	}
 
 	// Try to recover the note using the wrong private key - should fail
 	recoveredNote := frontend.TryRecoverNote(
 		encryptedNote,
 		*wrongPrivKey,
-		note.insertedIndex,
+		note.InsertedIndex,
 	)
 
 	// Should return nil since decryption should fail
@@ -0,0 +1,120 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/config"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/transaction"
+)
+
+func TestJoinSplit(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	senderKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	receiverKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+
+	firstDeposit, err := f.SendDeposit(&account, 4*1e6, client.ReceivingKey(*senderKey), *senderKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	secondDeposit, err := f.SendDeposit(&account, 5*1e6, client.ReceivingKey(*senderKey), *senderKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+
+	// a payment larger than any of the two notes
+	fromNotes := []*client.Note{firstDeposit.Note, secondDeposit.Note}
+	paymentAmount := uint64(7 * 1e6)
+	fee := uint64(config.WithdrawalMinFeeMultiplier*transaction.MinTxnFee +
+		circuits.JoinSplitInputs*config.NullifierMbr)
+	changeAmount := uint64(9*1e6) - paymentAmount - fee
+	_, err = f.SendJoinSplit(&client.JoinSplitOpts{
+		Fee:       fee,
+		FromNotes: fromNotes,
+		Outputs: []client.JoinSplitOutput{
+			{Amount: paymentAmount, Pubkey: client.ReceivingKey(*receiverKey)},
+			{Amount: changeAmount + 1, Pubkey: client.ReceivingKey(*senderKey)},
+		},
+	}, senderKey)
+	if err == nil {
+		t.Fatalf("Expected a join-split creating value to fail")
+	}
+	joinSplit, err := f.SendJoinSplit(&client.JoinSplitOpts{
+		Fee:       fee,
+		FromNotes: fromNotes,
+		Outputs: []client.JoinSplitOutput{
+			{Amount: paymentAmount, Pubkey: client.ReceivingKey(*receiverKey)},
+			{Amount: changeAmount, Pubkey: client.ReceivingKey(*senderKey)},
+		},
+	}, senderKey)
+	if err != nil {
+		t.Fatalf("Error making join-split: %s", err)
+	}
+	if joinSplit.Notes[1].InsertedIndex != joinSplit.Notes[0].InsertedIndex+1 {
+		t.Fatalf("Join-split notes not inserted one after the other")
+	}
+
+	// both owners discover their output from the chain
+	wallet := NewAppFrontend()
+	scanner, err := wallet.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	discovery, err := wallet.DiscoverNotes(context.Background(), events, *receiverKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Unspent) != 1 || discovery.Balances[0] != paymentAmount {
+		t.Fatalf("Expected the receiver to discover %d in 1 note, got %d in %d notes",
+			paymentAmount, discovery.Balances[0], len(discovery.Unspent))
+	}
+	discovery, err = wallet.DiscoverNotes(context.Background(), events, *senderKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Unspent) != 1 || discovery.Balances[0] != changeAmount {
+		t.Fatalf("Expected the sender to discover its change of %d, got %d in %d notes",
+			changeAmount, discovery.Balances[0], len(discovery.Unspent))
+	}
+
+	_, err = wallet.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  discovery.Unspent[0],
+	}, senderKey, client.ReceivingKey(*senderKey))
+	if err != nil {
+		t.Fatalf("Error withdrawing the change note: %s", err)
+	}
+
+	// the spent notes cannot be joined again
+	_, err = f.SendJoinSplit(&client.JoinSplitOpts{
+		Fee:       fee,
+		FromNotes: fromNotes,
+		Outputs: []client.JoinSplitOutput{
+			{Amount: paymentAmount, Pubkey: client.ReceivingKey(*receiverKey)},
+			{Amount: changeAmount, Pubkey: client.ReceivingKey(*senderKey)},
+		},
+	}, senderKey)
+	if err == nil {
+		t.Fatalf("Expected a double spend to fail")
+	}
+}
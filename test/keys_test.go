@@ -0,0 +1,123 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/keys"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestWalletKeys(t *testing.T) {
+	words, err := keys.NewMnemonic()
+	if err != nil {
+		t.Fatalf("Error generating mnemonic: %s", err)
+	}
+	wallet, err := keys.FromMnemonic(words)
+	if err != nil {
+		t.Fatalf("Error restoring wallet: %s", err)
+	}
+	restored, err := keys.FromMnemonic(words)
+	if err != nil {
+		t.Fatalf("Error restoring wallet: %s", err)
+	}
+	backup, err := restored.Mnemonic()
+	if err != nil || backup != words {
+		t.Fatalf("Wallet mnemonic does not round trip: %v", err)
+	}
+
+	key, err := wallet.SpendingKey(0, 1)
+	if err != nil {
+		t.Fatalf("Error deriving key: %s", err)
+	}
+	sameKey, err := restored.SpendingKey(0, 1)
+	if err != nil {
+		t.Fatalf("Error deriving key: %s", err)
+	}
+	if !key.PublicKey.Equal(&sameKey.PublicKey) {
+		t.Fatalf("Expected the same key from the same mnemonic")
+	}
+	otherKeys := map[string][2]uint32{"index": {0, 2}, "account": {1, 1}}
+	for name, path := range otherKeys {
+		other, err := wallet.SpendingKey(path[0], path[1])
+		if err != nil {
+			t.Fatalf("Error deriving key: %s", err)
+		}
+		if other.PublicKey.Equal(&key.PublicKey) {
+			t.Fatalf("Expected another key for another %s", name)
+		}
+	}
+	viewKey, err := wallet.ViewKey(0)
+	if err != nil {
+		t.Fatalf("Error deriving view key: %s", err)
+	}
+	if viewKey.PublicKey.Equal(&key.PublicKey) {
+		t.Fatalf("Expected the view key to differ from the spending keys")
+	}
+
+	if _, err := keys.FromMnemonic("not a mnemonic"); err == nil {
+		t.Fatalf("Expected an invalid mnemonic to fail")
+	}
+}
+
+func TestWalletRestore(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	words, err := keys.NewMnemonic()
+	if err != nil {
+		t.Fatalf("Error generating mnemonic: %s", err)
+	}
+	wallet, err := keys.FromMnemonic(words)
+	if err != nil {
+		t.Fatalf("Error restoring wallet: %s", err)
+	}
+
+	// the lost device received on its keys 0 and 2 of account 0
+	depositAmounts := map[uint32]uint64{0: 2 * 1e6, 2: 3 * 1e6}
+	for index, amount := range depositAmounts {
+		key, err := wallet.SpendingKey(0, index)
+		if err != nil {
+			t.Fatalf("Error deriving key: %s", err)
+		}
+		_, err = f.SendDeposit(&account, amount, client.ReceivingKey(*key), *key)
+		if err != nil {
+			t.Fatalf("Error making deposit: %s", err)
+		}
+	}
+
+	restored, err := keys.FromMnemonic(words)
+	if err != nil {
+		t.Fatalf("Error restoring wallet: %s", err)
+	}
+	device := NewAppFrontend()
+	scanner, err := device.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	discoveries, err := restored.Restore(context.Background(), device, events, 0, 5)
+	if err != nil {
+		t.Fatalf("Error restoring notes: %s", err)
+	}
+	if len(discoveries) != 3 {
+		t.Fatalf("Expected the discoveries of keys 0 to 2, got %d", len(discoveries))
+	}
+	for index, amount := range depositAmounts {
+		if discoveries[index].Balances[0] != amount {
+			t.Fatalf("Key %d balance %d, expected %d", index,
+				discoveries[index].Balances[0], amount)
+		}
+	}
+	if len(discoveries[1].Unspent) != 0 {
+		t.Fatalf("Expected no notes for key 1")
+	}
+}
@@ -10,6 +10,7 @@ import (
 	"testing"
 
 	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
 	"github.com/joe-p/Mithras-Protocol/config"
 	"github.com/joe-p/Mithras-Protocol/deployed"
 	"github.com/joe-p/Mithras-Protocol/setup"
@@ -22,8 +23,8 @@ import (
 )
 
 var (
-	f         *Frontend
-	dummyLsig *Lsig
+	f         *client.Frontend
+	dummyLsig *client.Lsig
 )
 
 const (
@@ -41,6 +42,16 @@ func generateTestKeyPair() (*eddsa.PrivateKey, error) {
 	return privateKey, nil
 }
 
+// NewAppFrontend creates a new Frontend for the app looking for the setup files in
+// the setup artefacts directory
+func NewAppFrontend() *client.Frontend {
+	frontend, err := client.NewFrontendFromDir(setup.ArtefactsDirPath, avm.GetAlgodClient())
+	if err != nil {
+		log.Fatalf("Error creating frontend: %v", err)
+	}
+	return frontend
+}
+
 func TestMain(m *testing.M) {
 	setup.CreateApp(deployed.DevNet)
 	f = NewAppFrontend()
@@ -50,7 +61,11 @@ func TestMain(m *testing.M) {
 		fmt.Printf("Error compiling dummy teal: %s", err)
 		os.Exit(1)
 	}
-	dummyLsig = readLogicSig(dummyLsigBytes)
+	dummyLsig, err = client.NewLsig(dummyLsigBytes)
+	if err != nil {
+		fmt.Printf("Error creating dummy lsig: %s", err)
+		os.Exit(1)
+	}
 
 	code := m.Run() // run all tests in package
 	// teardown if needed
@@ -77,7 +92,7 @@ func TestDepositWithdrawMBR(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Error generating test key pair: %s", err)
 	}
-	testPublicKey := testPrivKey.PublicKey
+	testPublicKey := client.ReceivingKey(*testPrivKey)
 
 	deposit, err := f.SendDeposit(&account, depositAmount, testPublicKey, *testPrivKey)
 	if err != nil {
@@ -90,12 +105,12 @@ func TestDepositWithdrawMBR(t *testing.T) {
 	withdrawalCount := 0
 	// let's make a withdrawal to a funded account
 	firstWithdrawalAmount := uint64(5 * 1e6)
-	firstWithdrawalOpts := &WithdrawalOpts{
-		recipient:    account.Address,
-		feeRecipient: account.Address,
-		feeSigner:    transaction.BasicAccountTransactionSigner{Account: account},
-		amount:       firstWithdrawalAmount,
-		fromNote:     deposit.Note,
+	firstWithdrawalOpts := &client.WithdrawalOpts{
+		Recipient:    account.Address,
+		FeeRecipient: account.Address,
+		FeeSigner:    transaction.BasicAccountTransactionSigner{Account: account},
+		Amount:       firstWithdrawalAmount,
+		FromNote:     deposit.Note,
 	}
 
 	// Attempt to withdrawal with the wrong key
@@ -104,7 +119,7 @@ func TestDepositWithdrawMBR(t *testing.T) {
 		t.Fatalf("Error generating new test key pair: %s", err)
 	}
 
-	_, err = f.SendWithdrawal(firstWithdrawalOpts, newKey, newKey.PublicKey)
+	_, err = f.SendWithdrawal(firstWithdrawalOpts, newKey, client.ReceivingKey(*newKey))
 	if err == nil {
 		t.Fatalf("Withdrawal should have failed with wrong key but it didn't")
 	} else {
@@ -124,13 +139,13 @@ func TestDepositWithdrawMBR(t *testing.T) {
 	// now let's make a withdrawal to a new account using the TSS, withdrawing everything
 	fee := config.WithdrawalMinFeeMultiplier*transaction.MinTxnFee + config.NullifierMbr
 	availableToWithdraw := depositAmount - firstWithdrawalAmount - uint64(2*fee)
-	secondWithdrawalOpts := &WithdrawalOpts{
-		recipient: newAccount.Address,
-		amount:    availableToWithdraw,
-		fromNote:  firstWithdrawal.Note,
+	secondWithdrawalOpts := &client.WithdrawalOpts{
+		Recipient: newAccount.Address,
+		Amount:    availableToWithdraw,
+		FromNote:  firstWithdrawal.Note,
 	}
 
-	secondWithdrawal, err := f.SendWithdrawal(secondWithdrawalOpts, testPrivKey, testPublicKey) 
+	secondWithdrawal, err := f.SendWithdrawal(secondWithdrawalOpts, testPrivKey, testPublicKey)
 
 	if err != nil {
 		t.Fatalf("Error making withdrawal: %s", err)
@@ -141,7 +156,7 @@ func TestDepositWithdrawMBR(t *testing.T) {
 
 	// Let's try one more withdrawal, it should fail because the last change is zero
 	thirdWithdrawalOpts := secondWithdrawalOpts
-	thirdWithdrawalOpts.amount = 1
+	thirdWithdrawalOpts.Amount = 1
 	_, err = f.SendWithdrawal(thirdWithdrawalOpts, testPrivKey, testPublicKey)
 	if err != nil {
 		fmt.Println("Error making withdrawal, as expected")
@@ -155,16 +170,16 @@ func TestDepositWithdrawMBR(t *testing.T) {
 		t.Fatalf("Error generating new key pair: %s", err)
 	}
 
-	deposit, err = f.SendDeposit(&account, 1000*1e6, newKeypair.PublicKey, *newKeypair)
+	deposit, err = f.SendDeposit(&account, 1000*1e6, client.ReceivingKey(*newKeypair), *newKeypair)
 	if err != nil {
 		t.Fatalf("Error making deposit: %s", err)
 	}
 	note := deposit.Note
 	newAccount = crypto.GenerateAccount()
-	withdrawalOpts := &WithdrawalOpts{
-		recipient: newAccount.Address,
-		amount:    0.1 * 1e6,
-		fromNote:  note,
+	withdrawalOpts := &client.WithdrawalOpts{
+		Recipient: newAccount.Address,
+		Amount:    0.1 * 1e6,
+		FromNote:  note,
 	}
 
 	for i := 1; i <= config.RootsCount*2; i++ {
@@ -173,7 +188,7 @@ func TestDepositWithdrawMBR(t *testing.T) {
 		if err != nil {
 			t.Fatalf("Error making withdrawal %d/100: %s", i, err)
 		}
-		withdrawalOpts.fromNote = w.Note
+		withdrawalOpts.FromNote = w.Note
 		withdrawalCount++
 	}
 
@@ -196,7 +211,7 @@ func TestWrongLsigVerifier(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Error generating test key pair: %s", err)
 	}
-	testPublicKey2 := testPrivKey2.PublicKey
+	testPublicKey2 := client.ReceivingKey(*testPrivKey2)
 
 	depositAmount := uint64(10 * 1e6)
 	depositLsig := f.App.DepositVerifier
@@ -219,10 +234,10 @@ func TestWrongLsigVerifier(t *testing.T) {
 
 	withdrawalLsig := f.App.WithdrawalVerifier
 	f.App.WithdrawalVerifier = dummyLsig
-	_, err = f.SendWithdrawal(&WithdrawalOpts{
-		recipient: account.Address,
-		amount:    depositAmount - 1*1e6,
-		fromNote:  deposit.Note,
+	_, err = f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    depositAmount - 1*1e6,
+		FromNote:  deposit.Note,
 	}, testPrivKey2, testPublicKey2)
 	if err == nil {
 		t.Fatalf("Ouch, no error making withdrawal with dummy lsig: %s", err)
@@ -239,7 +254,7 @@ func TestWithdrawToAddressBiggerThanMod(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Error generating test key pair: %s", err)
 	}
-	testPublicKey3 := testPrivKey3.PublicKey
+	testPublicKey3 := client.ReceivingKey(*testPrivKey3)
 
 	// let's make a deposit
 	depositorAccount := crypto.GenerateAccount()
@@ -255,10 +270,10 @@ func TestWithdrawToAddressBiggerThanMod(t *testing.T) {
 
 	// let's try to make a withdrawal to the bigger than mod address
 	withdrawAmount := uint64(1 * 1e6)
-	withdrawalOpts := &WithdrawalOpts{
-		recipient: biggerThanModAddress,
-		amount:    withdrawAmount,
-		fromNote:  deposit.Note,
+	withdrawalOpts := &client.WithdrawalOpts{
+		Recipient: biggerThanModAddress,
+		Amount:    withdrawAmount,
+		FromNote:  deposit.Note,
 	}
 	withdrawal, err := f.SendWithdrawal(withdrawalOpts, testPrivKey3, testPublicKey3)
 	if err != nil {
@@ -0,0 +1,80 @@
+package test
+
+import (
+	"context"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/circuits"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/prover"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestRemoteProver(t *testing.T) {
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	server := prover.NewServer(f.App, prover.Config{Private: true})
+	server.Start(ctx)
+	ts := httptest.NewServer(server)
+	defer ts.Close()
+
+	// the shared frontend proves with the daemon for the duration of the test, so
+	// that its tree stays in sync with the app
+	remote := &client.RemoteProver{URL: ts.URL}
+	f.Prover = remote
+	defer func() { f.Prover = nil }()
+
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+
+	deposit, err := f.SendDeposit(&account, 10*1e6, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit with remote prover: %s", err)
+	}
+
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error making withdrawal with remote prover: %s", err)
+	}
+	if withdrawal.Note.InsertedIndex != deposit.Note.InsertedIndex+1 {
+		t.Fatalf("Withdrawal change note not inserted after the deposit")
+	}
+	if _, err := f.SendTransfer(&client.TransferOpts{
+		Amount:   1 * 1e6,
+		FromNote: withdrawal.Note,
+	}, privKey, client.ReceivingKey(*privKey)); err != nil {
+		t.Fatalf("Error making transfer with remote prover: %s", err)
+	}
+
+	// unknown methods and unsatisfied assignments are rejected
+	wrongCommitment := &circuits.DepositCircuit{
+		Amount:     1,
+		AssetId:    0,
+		Commitment: 2,
+		OutputX:    3,
+		OutputY:    4,
+		K:          5,
+		R:          6,
+	}
+	if _, err := remote.Prove(ctx, "unknown", wrongCommitment); err == nil {
+		t.Fatalf("Expected an unknown method to fail")
+	}
+	if _, err := remote.Prove(ctx, client.DepositMethod, wrongCommitment); err == nil {
+		t.Fatalf("Expected a wrong commitment to fail")
+	}
+}
+
@@ -0,0 +1,72 @@
+package test
+
+import (
+	"context"
+	"encoding/json"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestDepositReceipt(t *testing.T) {
+	ngo := crypto.GenerateAccount()
+	err := avm.EnsureFunded(ngo.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	recipientKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&ngo, 20*1e6, client.ReceivingKey(*recipientKey), *recipientKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+
+	receipt, err := f.NewDepositReceipt(deposit, &ngo, true)
+	if err != nil {
+		t.Fatalf("Error creating receipt: %s", err)
+	}
+	// the auditor gets the receipt as JSON and verifies it with its own frontend
+	data, err := json.Marshal(receipt)
+	if err != nil {
+		t.Fatalf("Error encoding receipt: %s", err)
+	}
+	audited := &client.DepositReceipt{}
+	if err := json.Unmarshal(data, audited); err != nil {
+		t.Fatalf("Error decoding receipt: %s", err)
+	}
+	auditor := NewAppFrontend()
+	source := &client.BlockSource{Client: avm.GetAlgodClient()}
+	ctx := context.Background()
+	if err := auditor.VerifyDepositReceipt(ctx, source, audited); err != nil {
+		t.Fatalf("Error verifying receipt: %s", err)
+	}
+
+	// a receipt claiming a larger amount, signed by the depositor, fails against the chain
+	forged := *receipt
+	forged.Amount *= 2
+	forged.Disclosure = nil
+	forged.Signature, err = crypto.SignBytes(ngo.PrivateKey, forged.SignedBytes())
+	if err != nil {
+		t.Fatalf("Error signing receipt: %s", err)
+	}
+	if auditor.VerifyDepositReceipt(ctx, source, &forged) == nil {
+		t.Fatalf("Expected a receipt with a wrong amount to fail")
+	}
+
+	// someone else cannot claim the deposit
+	other := crypto.GenerateAccount()
+	forged = *receipt
+	forged.Depositor = other.Address.String()
+	forged.Signature, err = crypto.SignBytes(other.PrivateKey, forged.SignedBytes())
+	if err != nil {
+		t.Fatalf("Error signing receipt: %s", err)
+	}
+	if auditor.VerifyDepositReceipt(ctx, source, &forged) == nil {
+		t.Fatalf("Expected a receipt of another depositor to fail")
+	}
+}
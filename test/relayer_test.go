@@ -0,0 +1,93 @@
+package test
+
+import (
+	"bytes"
+	"context"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/relayer"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestRelayedWithdrawal(t *testing.T) {
+	ts := httptest.NewServer(relayer.NewServer(NewAppFrontend(), relayer.Config{}))
+	defer ts.Close()
+	remote := &client.RemoteRelayer{URL: ts.URL}
+	ctx := context.Background()
+
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&account, 10*1e6, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+
+	// withdraw to a new address, which has no Algo to pay the fees
+	newAccount := crypto.GenerateAccount()
+	opts := &client.WithdrawalOpts{
+		Recipient: newAccount.Address,
+		Amount:    2 * 1e6,
+		FromNote:  deposit.Note,
+	}
+
+	// a fee below the relayer fee is rejected
+	opts.Fee = client.DefaultWithdrawalFee(0) - 1
+	req, _, err := f.PrepareWithdrawal(opts, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error preparing withdrawal: %s", err)
+	}
+	if _, err := remote.Relay(ctx, req); err == nil {
+		t.Fatalf("Expected a withdrawal with a low fee to fail")
+	}
+
+	opts.Fee = 0
+	req, withdrawal, err := f.PrepareWithdrawal(opts, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error preparing withdrawal: %s", err)
+	}
+	res, err := remote.Relay(ctx, req)
+	if err != nil {
+		t.Fatalf("Error relaying withdrawal: %s", err)
+	}
+	f.AddRelayedWithdrawal(req, withdrawal, res)
+	if !bytes.Equal(res.Root, f.Tree.Root()) {
+		t.Fatalf("Relayed root does not match the local tree")
+	}
+
+	accountInfo, err := avm.GetAlgodClient().AccountInformation(newAccount.Address.String()).
+		Do(ctx)
+	if err != nil {
+		t.Fatalf("Error fetching account information: %s", err)
+	}
+	if accountInfo.Amount != opts.Amount {
+		t.Fatalf("Expected balance %d, got %d", opts.Amount, accountInfo.Amount)
+	}
+
+	// the same withdrawal cannot be relayed twice
+	if _, err := remote.Relay(ctx, req); err == nil {
+		t.Fatalf("Expected a spent nullifier to fail")
+	}
+
+	// the change note can be withdrawn again through the relayer
+	opts.FromNote = withdrawal.Note
+	req, withdrawal, err = f.PrepareWithdrawal(opts, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error preparing withdrawal: %s", err)
+	}
+	res, err = remote.Relay(ctx, req)
+	if err != nil {
+		t.Fatalf("Error relaying second withdrawal: %s", err)
+	}
+	f.AddRelayedWithdrawal(req, withdrawal, res)
+}
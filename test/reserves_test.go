@@ -0,0 +1,65 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestCheckReserves(t *testing.T) {
+	ctx := context.Background()
+	source := &client.BlockSource{Client: avm.GetAlgodClient()}
+
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&account, 10*1e6, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	_, err = f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    2 * 1e6,
+		FromNote:  deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error making withdrawal: %s", err)
+	}
+
+	reserves, err := f.CheckReserves(ctx, source)
+	if err != nil {
+		t.Fatalf("Error checking reserves: %s", err)
+	}
+	if len(reserves.Discrepancies) != 0 {
+		t.Fatalf("Unexpected discrepancies: %v", reserves.Discrepancies)
+	}
+	if reserves.Nullifiers == 0 || reserves.Assets[0].Deposited < 10*1e6 {
+		t.Fatalf("Expected the deposit and the withdrawal in the reserves: %+v",
+			reserves.Assets[0])
+	}
+
+	// a payment to the app outside its methods is reported as a surplus
+	appAddress := crypto.GetApplicationAddress(f.App.Id).String()
+	err = avm.EnsureFunded(appAddress, reserves.Assets[0].Balance+1e6)
+	if err != nil {
+		t.Fatalf("Error funding app: %s", err)
+	}
+	reserves, err = f.CheckReserves(ctx, source)
+	if err != nil {
+		t.Fatalf("Error checking reserves: %s", err)
+	}
+	if reserves.Assets[0].Surplus != 1e6 || len(reserves.Discrepancies) != 1 {
+		t.Fatalf("Expected a surplus of 1 Algo, got %d: %v", reserves.Assets[0].Surplus,
+			reserves.Discrepancies)
+	}
+}
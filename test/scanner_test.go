@@ -0,0 +1,63 @@
+package test
+
+import (
+	"bytes"
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+)
+
+func TestScannerRebuildsTree(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	deposit, err := f.SendDeposit(&account, 10*1e6, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+
+	// a fresh frontend knows nothing of the deposit until it scans the chain
+	fresh := NewAppFrontend()
+	scanner, err := fresh.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	if len(events) == 0 {
+		t.Fatalf("Expected at least one event")
+	}
+
+	root, err := fresh.GetRoot()
+	if err != nil {
+		t.Fatalf("Error reading root: %s", err)
+	}
+	if !bytes.Equal(root, fresh.Tree.Root()) {
+		t.Fatalf("Rebuilt root %x does not match onchain root %x", fresh.Tree.Root(), root)
+	}
+
+	commitment, err := fresh.Tree.LeafHash(deposit.Note.InsertedIndex)
+	if err != nil {
+		t.Fatalf("Deposit leaf not found: %s", err)
+	}
+	if !bytes.Equal(commitment, deposit.Note.Commitment) {
+		t.Fatalf("Deposit commitment mismatch at index %d", deposit.Note.InsertedIndex)
+	}
+
+	// a new scan only reads the new history
+	if _, err = scanner.Scan(context.Background()); err != nil {
+		t.Fatalf("Error rescanning: %s", err)
+	}
+}
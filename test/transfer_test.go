@@ -0,0 +1,120 @@
+package test
+
+import (
+	"bytes"
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/algorand/go-algorand-sdk/v2/types"
+)
+
+// algoBalance returns the microalgo held by address
+func algoBalance(address types.Address) (uint64, error) {
+	info, err := avm.GetAlgodClient().AccountInformation(address.String()).
+		Do(context.Background())
+	if err != nil {
+		return 0, err
+	}
+	return info.Amount, nil
+}
+
+func TestTransfer(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	senderKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	receiverKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+
+	depositAmount := uint64(10 * 1e6)
+	deposit, err := f.SendDeposit(&account, depositAmount, client.ReceivingKey(*senderKey), *senderKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+
+	balanceBefore, err := algoBalance(account.Address)
+	if err != nil {
+		t.Fatalf("Error getting balance: %s", err)
+	}
+	transferAmount := uint64(3 * 1e6)
+	transfer, err := f.SendTransfer(&client.TransferOpts{
+		Amount:   transferAmount,
+		FromNote: deposit.Note,
+	}, senderKey, client.ReceivingKey(*receiverKey))
+	if err != nil {
+		t.Fatalf("Error making transfer: %s", err)
+	}
+	if transfer.SentNote.Amount != transferAmount {
+		t.Fatalf("Sent note amount %d, expected %d", transfer.SentNote.Amount, transferAmount)
+	}
+	balanceAfter, err := algoBalance(account.Address)
+	if err != nil {
+		t.Fatalf("Error getting balance: %s", err)
+	}
+	if balanceAfter != balanceBefore {
+		t.Fatalf("Transfer changed the account balance from %d to %d", balanceBefore,
+			balanceAfter)
+	}
+
+	// the receiver discovers its note from the chain and withdraws it
+	wallet := NewAppFrontend()
+	scanner, err := wallet.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	discovery, err := wallet.DiscoverNotes(context.Background(), events, *receiverKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Unspent) != 1 || discovery.Balances[0] != transferAmount {
+		t.Fatalf("Expected the receiver to discover %d in 1 note, got %d in %d notes",
+			transferAmount, discovery.Balances[0], len(discovery.Unspent))
+	}
+	received := discovery.Unspent[0]
+	if received.InsertedIndex != transfer.SentNote.InsertedIndex ||
+		!bytes.Equal(received.Commitment, transfer.SentNote.Commitment) {
+		t.Fatalf("Discovered note does not match the sent note")
+	}
+
+	// the sender discovers its change
+	discovery, err = wallet.DiscoverNotes(context.Background(), events, *senderKey)
+	if err != nil {
+		t.Fatalf("Error discovering notes: %s", err)
+	}
+	if len(discovery.Unspent) != 1 || discovery.Unspent[0].Amount != transfer.Note.Amount {
+		t.Fatalf("Expected the sender to discover its change of %d", transfer.Note.Amount)
+	}
+
+	_, err = wallet.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  received,
+	}, receiverKey, client.ReceivingKey(*receiverKey))
+	if err != nil {
+		t.Fatalf("Error withdrawing the received note: %s", err)
+	}
+
+	// the spent note cannot be transferred again
+	_, err = f.SendTransfer(&client.TransferOpts{
+		Amount:   transferAmount,
+		FromNote: deposit.Note,
+	}, senderKey, client.ReceivingKey(*receiverKey))
+	if err == nil {
+		t.Fatalf("Expected a double spend to fail")
+	}
+}
@@ -0,0 +1,96 @@
+package test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/avm"
+	"github.com/joe-p/Mithras-Protocol/client"
+
+	"github.com/algorand/go-algorand-sdk/v2/crypto"
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+func TestAuditHistory(t *testing.T) {
+	account := crypto.GenerateAccount()
+	err := avm.EnsureFunded(account.Address.String(), 100*1e6)
+	if err != nil {
+		t.Fatalf("Error funding account: %s", err)
+	}
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	viewKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating view key pair: %s", err)
+	}
+
+	// the shared frontend plays the NGO, whose notes are all encrypted to the view key
+	f.ViewPubkey = &viewKey.PublicKey
+	defer func() { f.ViewPubkey = nil }()
+
+	depositAmount := uint64(10 * 1e6)
+	deposit, err := f.SendDeposit(&account, depositAmount, client.ReceivingKey(*privKey), *privKey)
+	if err != nil {
+		t.Fatalf("Error making deposit: %s", err)
+	}
+	withdrawal, err := f.SendWithdrawal(&client.WithdrawalOpts{
+		Recipient: account.Address,
+		Amount:    1 * 1e6,
+		FromNote:  deposit.Note,
+	}, privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error making withdrawal: %s", err)
+	}
+
+	auditor := NewAppFrontend()
+	scanner, err := auditor.NewScanner(&client.BlockSource{Client: avm.GetAlgodClient()})
+	if err != nil {
+		t.Fatalf("Error creating scanner: %s", err)
+	}
+	events, err := scanner.Scan(context.Background())
+	if err != nil {
+		t.Fatalf("Error scanning: %s", err)
+	}
+	// without the receiver nullifier key the auditor cannot tell spent notes
+	history, err := auditor.AuditHistory(context.Background(), events, *viewKey, nil)
+	if err != nil {
+		t.Fatalf("Error auditing history: %s", err)
+	}
+	if len(history) != 2 || history[0].NullifierKnown || history[0].Spent {
+		t.Fatalf("Expected 2 viewed notes with unknown nullifiers")
+	}
+
+	nullifierKeys := map[eddsa.PublicKey]client.NullifierKey{
+		client.ReceivingKey(*privKey): client.NewNullifierKey(*privKey),
+	}
+	history, err = auditor.AuditHistory(context.Background(), events, *viewKey, nullifierKeys)
+	if err != nil {
+		t.Fatalf("Error auditing history: %s", err)
+	}
+	if len(history) != 2 {
+		t.Fatalf("Expected 2 viewed notes, got %d", len(history))
+	}
+	if history[0].InsertedIndex != deposit.Note.InsertedIndex || history[0].Amount != depositAmount ||
+		!history[0].Spent {
+		t.Fatalf("Expected the spent deposit note first")
+	}
+	if history[1].InsertedIndex != withdrawal.Note.InsertedIndex ||
+		history[1].Amount != withdrawal.Note.Amount || history[1].Spent {
+		t.Fatalf("Expected the unspent change note second")
+	}
+	receivingKey := client.ReceivingKey(*privKey)
+	if !history[0].Receiver.Equal(&receivingKey) || !history[1].Sender.Equal(&receivingKey) {
+		t.Fatalf("Viewed notes sender or receiver mismatch")
+	}
+
+	// the spending key owner's view key is not the auditor key
+	history, err = auditor.AuditHistory(context.Background(), events, *privKey, nullifierKeys)
+	if err != nil {
+		t.Fatalf("Error auditing history: %s", err)
+	}
+	if len(history) != 0 {
+		t.Fatalf("Expected no viewed notes for a different key, got %d", len(history))
+	}
+}
@@ -0,0 +1,71 @@
+package test
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/wallet"
+)
+
+func TestWalletStore(t *testing.T) {
+	privKey, err := generateTestKeyPair()
+	if err != nil {
+		t.Fatalf("Error generating test key pair: %s", err)
+	}
+	note, _, err := f.NewNote(1000, *privKey, client.ReceivingKey(*privKey))
+	if err != nil {
+		t.Fatalf("Error creating note: %s", err)
+	}
+	note.InsertedIndex = 4
+
+	store := wallet.NewStore(f.App.Id)
+	store.AddKey(privKey, 0, 3)
+	store.AddDiscovery(&client.Discovery{Unspent: []*client.Note{note}})
+	store.LastRound = 42
+
+	path := filepath.Join(t.TempDir(), "wallet")
+	password := []byte("correct horse")
+	if err := store.Save(path, password); err != nil {
+		t.Fatalf("Error saving wallet: %s", err)
+	}
+	// saving again replaces the file without leaving temporary files
+	if err := store.Save(path, password); err != nil {
+		t.Fatalf("Error saving wallet: %s", err)
+	}
+	entries, err := os.ReadDir(filepath.Dir(path))
+	if err != nil || len(entries) != 1 {
+		t.Fatalf("Expected the wallet file only, got %d entries", len(entries))
+	}
+
+	if _, err := wallet.Load(path, []byte("wrong password")); err == nil {
+		t.Fatalf("Expected a wrong password to fail")
+	}
+	loaded, err := wallet.Load(path, password)
+	if err != nil {
+		t.Fatalf("Error loading wallet: %s", err)
+	}
+	if loaded.AppId != f.App.Id || loaded.LastRound != 42 || len(loaded.Keys) != 1 ||
+		len(loaded.Notes) != 1 {
+		t.Fatalf("Loaded wallet does not match the saved one")
+	}
+	key, err := loaded.Keys[0].PrivateKey()
+	if err != nil || !key.PublicKey.Equal(&privKey.PublicKey) || loaded.Keys[0].Index != 3 {
+		t.Fatalf("Loaded key does not match: %v", err)
+	}
+	loadedNote := loaded.Notes[0]
+	if loadedNote.Spent || loadedNote.InsertedIndex != 4 || loadedNote.Amount != 1000 ||
+		!bytes.Equal(loadedNote.K, note.K) || !bytes.Equal(loadedNote.R, note.R) ||
+		!bytes.Equal(f.MakeCommitment(loadedNote.Amount, loadedNote.AssetId, loadedNote.K,
+			loadedNote.R, client.ReceivingKey(*privKey)), note.Commitment) {
+		t.Fatalf("Loaded note does not match the saved one")
+	}
+
+	// a later discovery marks the note spent
+	loaded.AddDiscovery(&client.Discovery{Spent: []*client.Note{note}})
+	if len(loaded.Notes) != 1 || !loaded.Notes[0].Spent || len(loaded.Unspent()) != 0 {
+		t.Fatalf("Expected the stored note to be marked spent")
+	}
+}
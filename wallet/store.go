@@ -0,0 +1,173 @@
+// Package wallet persists the keys and notes of a Mithras wallet in a file encrypted
+// with a password, see Store.
+package wallet
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/joe-p/Mithras-Protocol/client"
+	"github.com/joe-p/Mithras-Protocol/encrypt"
+
+	"github.com/consensys/gnark-crypto/ecc/bls12-381/twistededwards/eddsa"
+)
+
+// StoreVersion is the version of the wallet file format
+const StoreVersion = 1
+
+// Store is the content of a wallet file: the keys and notes of the wallet and the
+// state of its scan. The file holds the JSON serialization of Store encrypted with
+// encrypt.EncryptWithPassword (scrypt and secretbox), and is replaced atomically on
+// Save so that a crash leaves either the previous or the new version.
+type Store struct {
+	Version int
+
+	// AppId is the id of the Mithras application of the notes
+	AppId uint64
+	// Seed is the seed of the keys package wallet the keys are derived from, if any
+	Seed []byte `json:",omitempty"`
+	Keys []*Key
+	// NextNoteIndex is the Index of the frontend NoteDerivation, if used, to save
+	// after each confirmed group only
+	NextNoteIndex uint64
+	Notes         []*Note
+	// LastRound is the last round scanned, as Scanner.LastRound
+	LastRound uint64
+}
+
+// Key is a spending key of the wallet, with its derivation path if it is derived
+// from the wallet seed
+type Key struct {
+	Account uint32
+	Index   uint32
+	Private []byte // eddsa.PrivateKey.Bytes()
+}
+
+// Note is a note of the wallet with its spent status. The embedded client.Note holds
+// the note secrets and its leaf index.
+type Note struct {
+	*client.Note
+	Spent bool
+}
+
+// NewStore returns an empty store for the notes of appId
+func NewStore(appId uint64) *Store {
+	return &Store{Version: StoreVersion, AppId: appId}
+}
+
+// Load reads and decrypts with password the wallet file at path
+func Load(path string, password []byte) (*Store, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read wallet file: %v", err)
+	}
+	plaintext, err := encrypt.DecryptWithPassword(data, password)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt wallet file: %v", err)
+	}
+	s := &Store{}
+	if err := json.Unmarshal(plaintext, s); err != nil {
+		return nil, fmt.Errorf("failed to decode wallet file: %v", err)
+	}
+	if s.Version != StoreVersion {
+		return nil, fmt.Errorf("unsupported wallet file version %d", s.Version)
+	}
+	return s, nil
+}
+
+// Save encrypts the store with password and writes it to path. The new content is
+// written and synced to a temporary file in the same directory, which then replaces
+// path.
+func (s *Store) Save(path string, password []byte) error {
+	plaintext, err := json.Marshal(s)
+	if err != nil {
+		return fmt.Errorf("failed to encode wallet: %v", err)
+	}
+	data, err := encrypt.EncryptWithPassword(plaintext, password)
+	if err != nil {
+		return fmt.Errorf("failed to encrypt wallet: %v", err)
+	}
+
+	dir := filepath.Dir(path)
+	tmp, err := os.CreateTemp(dir, filepath.Base(path)+".tmp-*")
+	if err != nil {
+		return fmt.Errorf("failed to create wallet file: %v", err)
+	}
+	defer os.Remove(tmp.Name()) // no-op after the rename
+
+	if _, err := tmp.Write(data); err != nil {
+		tmp.Close()
+		return fmt.Errorf("failed to write wallet file: %v", err)
+	}
+	if err := tmp.Sync(); err != nil {
+		tmp.Close()
+		return fmt.Errorf("failed to sync wallet file: %v", err)
+	}
+	if err := tmp.Close(); err != nil {
+		return fmt.Errorf("failed to close wallet file: %v", err)
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		return fmt.Errorf("failed to replace wallet file: %v", err)
+	}
+	// sync the directory so that the rename itself is durable
+	if d, err := os.Open(dir); err == nil {
+		d.Sync()
+		d.Close()
+	}
+	return nil
+}
+
+// AddKey adds privkey at path account/index to the store
+func (s *Store) AddKey(privkey *eddsa.PrivateKey, account, index uint32) {
+	s.Keys = append(s.Keys, &Key{
+		Account: account,
+		Index:   index,
+		Private: privkey.Bytes(),
+	})
+}
+
+// PrivateKey decodes the key
+func (k *Key) PrivateKey() (*eddsa.PrivateKey, error) {
+	privkey := &eddsa.PrivateKey{}
+	if _, err := privkey.SetBytes(k.Private); err != nil {
+		return nil, fmt.Errorf("invalid private key: %v", err)
+	}
+	return privkey, nil
+}
+
+// AddDiscovery adds the notes of discovery to the store, or updates their spent
+// status if they are already stored
+func (s *Store) AddDiscovery(discovery *client.Discovery) {
+	for _, note := range discovery.Unspent {
+		s.addNote(note, false)
+	}
+	for _, note := range discovery.Spent {
+		s.addNote(note, true)
+	}
+}
+
+// addNote adds note with its spent status, or updates the status of the stored note
+// with the same commitment. A spent note stays spent.
+func (s *Store) addNote(note *client.Note, spent bool) {
+	for _, stored := range s.Notes {
+		if bytes.Equal(stored.Commitment, note.Commitment) {
+			stored.Spent = stored.Spent || spent
+			return
+		}
+	}
+	s.Notes = append(s.Notes, &Note{Note: note, Spent: spent})
+}
+
+// Unspent returns the unspent notes of the store
+func (s *Store) Unspent() []*client.Note {
+	var unspent []*client.Note
+	for _, note := range s.Notes {
+		if !note.Spent {
+			unspent = append(unspent, note.Note)
+		}
+	}
+	return unspent
+}